@@ -0,0 +1,61 @@
+use std::collections::HashMap;
+
+use itertools::Itertools;
+
+crate::day_module!(1, parse = parse);
+
+/// The left and right location lists.
+pub type Lists = (Vec<u32>, Vec<u32>);
+
+pub fn parse(input: &str) -> Lists {
+  input
+    .lines()
+    .map(|line| line.split_once(' '))
+    .map(|tups| tups.expect("invalid input"))
+    .map(|(x, y)| {
+      (
+        x.trim().parse::<u32>().expect("couln't parse u32"),
+        y.trim().parse::<u32>().expect("couldn't parse u32"),
+      )
+    })
+    .unzip()
+}
+
+pub fn part_one((left, right): &Lists) -> Option<u32> {
+  let output = left
+    .iter()
+    .sorted()
+    .zip(right.iter().sorted())
+    .fold(0u32, |acc, (lhs, rhs)| acc + lhs.abs_diff(*rhs));
+  Some(output)
+}
+
+pub fn part_two((left, right): &Lists) -> Option<u32> {
+  let freqs: HashMap<u32, u32> = right.iter().fold(HashMap::new(), |mut map, val| {
+    *map.entry(*val).or_default() += 1;
+    map
+  });
+  Some(
+    left
+      .iter()
+      .map(|val| val * freqs.get(val).unwrap_or(&0))
+      .sum(),
+  )
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_part_one() {
+    let result = part_one(&parse(&crate::template::read_file("examples", DAY)));
+    assert_eq!(result, None);
+  }
+
+  #[test]
+  fn test_part_two() {
+    let result = part_two(&parse(&crate::template::read_file("examples", DAY)));
+    assert_eq!(result, None);
+  }
+}
@@ -0,0 +1,20 @@
+//! The solution for each day, e.g. `advent_of_code::days::day01::part_one`.
+//! The binaries in `src/bin` run these with the [`solution!`](crate::solution) macro,
+//! [`solve`] runs them in-process with the `solutions` feature, e.g. for `cargo serve`.
+
+pub mod day01;
+pub mod day02;
+pub mod day03;
+pub mod day04;
+pub mod day05;
+pub mod day06;
+pub mod day08;
+pub mod day09;
+pub mod day10;
+pub mod day11;
+pub mod day13;
+pub mod day14;
+
+crate::solutions!(
+  day01, day02, day03, day04, day05, day06, day08, day09, day10, day11, day13, day14,
+);
@@ -1,14 +1,19 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{
+    all, download, inputs, puzzles, read, scaffold, solve, time,
+};
 use args::{parse, AppArguments};
 
+#[cfg(feature = "solutions")]
+use advent_of_code::template::commands::serve;
 #[cfg(feature = "today")]
-use advent_of_code::template::Day;
-#[cfg(feature = "today")]
-use std::process;
+use advent_of_code::template::commands::today;
 
 mod args {
+    use advent_of_code::template::commands::inputs::InputsCommand;
+    use advent_of_code::template::commands::puzzles::PuzzlesCommand;
+    use advent_of_code::template::commands::time::Comparison;
     use advent_of_code::template::Day;
-    use std::process;
+    use std::{fmt::Display, path::PathBuf, process, str::FromStr};
 
     pub enum AppArguments {
         Download {
@@ -17,27 +22,49 @@ mod args {
         Read {
             day: Day,
         },
+        Inputs {
+            command: InputsCommand,
+        },
+        Puzzles {
+            command: PuzzlesCommand,
+        },
         Scaffold {
             day: Day,
             download: bool,
             overwrite: bool,
+            dry_run: bool,
+            restore: bool,
         },
         Solve {
             day: Day,
             release: bool,
             dhat: bool,
+            alloc: bool,
+            visualize: bool,
+            trace: bool,
             submit: Option<u8>,
+            inputs: Option<PathBuf>,
         },
         All {
             release: bool,
+            junit: Option<PathBuf>,
+            tap: Option<PathBuf>,
         },
         Time {
             all: bool,
             day: Option<Day>,
             store: bool,
+            alloc: bool,
+            comparison: Comparison,
+        },
+        #[cfg(feature = "solutions")]
+        Serve {
+            port: u16,
         },
         #[cfg(feature = "today")]
-        Today,
+        Today {
+            wait: bool,
+        },
     }
 
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
@@ -46,36 +73,103 @@ mod args {
         let app_args = match args.subcommand()?.as_deref() {
             Some("all") => AppArguments::All {
                 release: args.contains("--release"),
+                junit: args.opt_value_from_str("--junit")?,
+                tap: args.opt_value_from_str("--tap")?,
             },
             Some("time") => {
                 let all = args.contains("--all");
                 let store = args.contains("--store");
+                let alloc = args.contains("--alloc");
+                let profiles: Option<Vec<String>> = args.opt_value_from_fn("--profiles", list)?;
+                let threads: Option<Vec<usize>> = args.opt_value_from_fn("--threads", list)?;
+
+                let comparison = match (profiles, threads) {
+                    (Some(_), Some(_)) => {
+                        eprintln!("`--profiles` and `--threads` cannot be combined.");
+                        process::exit(1);
+                    }
+                    // the timings with a smaller thread pool would replace the stored ones.
+                    (None, Some(_)) if store => {
+                        eprintln!("`--store` cannot be combined with `--threads`.");
+                        process::exit(1);
+                    }
+                    (None, Some(threads)) if threads.contains(&0) => {
+                        eprintln!("Thread counts have to be at least 1.");
+                        process::exit(1);
+                    }
+                    (Some(profiles), None) => Comparison::Profiles(profiles),
+                    (None, Some(threads)) => Comparison::Threads(threads),
+                    (None, None) => Comparison::None,
+                };
 
                 AppArguments::Time {
                     all,
                     day: args.opt_free_from_str()?,
                     store,
+                    alloc,
+                    comparison,
                 }
             }
+            #[cfg(feature = "solutions")]
+            Some("serve") => AppArguments::Serve {
+                port: args
+                    .opt_value_from_str("--port")?
+                    .unwrap_or(advent_of_code::template::commands::serve::DEFAULT_PORT),
+            },
             Some("download") => AppArguments::Download {
                 day: args.free_from_str()?,
             },
             Some("read") => AppArguments::Read {
                 day: args.free_from_str()?,
             },
+            Some("inputs") => AppArguments::Inputs {
+                command: parse_inputs_command(&mut args)?,
+            },
+            Some("puzzles") => AppArguments::Puzzles {
+                command: parse_puzzles_command(&mut args)?,
+            },
             Some("scaffold") => AppArguments::Scaffold {
                 day: args.free_from_str()?,
                 download: args.contains("--download"),
                 overwrite: args.contains("--overwrite"),
+                dry_run: args.contains("--dry-run"),
+                restore: args.contains("--restore"),
             },
-            Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
-                release: args.contains("--release"),
-                submit: args.opt_value_from_str("--submit")?,
-                dhat: args.contains("--dhat"),
-            },
+            Some("solve") => {
+                let day = args.free_from_str()?;
+                let release = args.contains("--release");
+                let submit = args.opt_value_from_str("--submit")?;
+                let dhat = args.contains("--dhat");
+                let alloc = args.contains("--alloc");
+                let visualize = args.contains("--visualize");
+                let trace = args.contains("--trace");
+                let inputs = args.opt_value_from_str("--inputs")?;
+
+                // the inputs are solved in-process, so none of the flags for the binary apply.
+                if inputs.is_some()
+                    && (release || dhat || alloc || visualize || trace || submit.is_some())
+                {
+                    eprintln!(
+                        "`--inputs` cannot be combined with `--release`, `--dhat`, `--alloc`, `--visualize`, `--trace` or `--submit`."
+                    );
+                    process::exit(1);
+                }
+
+                AppArguments::Solve {
+                    day,
+                    release,
+                    dhat,
+                    alloc,
+                    visualize,
+                    trace,
+                    submit,
+                    inputs,
+                }
+            }
             #[cfg(feature = "today")]
-            Some("today") => AppArguments::Today,
+            Some("today") => AppArguments::Today {
+                wait: args.contains("--wait"),
+            },
             Some(x) => {
                 eprintln!("Unknown command: {x}");
                 process::exit(1);
@@ -93,6 +187,94 @@ mod args {
 
         Ok(app_args)
     }
+
+    fn parse_inputs_command(
+        args: &mut pico_args::Arguments,
+    ) -> Result<InputsCommand, Box<dyn std::error::Error>> {
+        let command = match args.subcommand()?.as_deref() {
+            Some("verify") => InputsCommand::Verify {
+                day: args.opt_free_from_str()?,
+            },
+            Some("restore") => InputsCommand::Restore {
+                day: args.opt_free_from_str()?,
+            },
+            Some("encrypt") => InputsCommand::Encrypt,
+            Some("decrypt") => InputsCommand::Decrypt,
+            Some("rekey") => InputsCommand::Rekey,
+            Some(x) => {
+                eprintln!("Unknown inputs command: {x}");
+                process::exit(1);
+            }
+            None => {
+                eprintln!(
+                    "No inputs command specified. Use `verify`, `restore`, `encrypt`, `decrypt` or `rekey`."
+                );
+                process::exit(1);
+            }
+        };
+
+        Ok(command)
+    }
+
+    fn parse_puzzles_command(
+        args: &mut pico_args::Arguments,
+    ) -> Result<PuzzlesCommand, Box<dyn std::error::Error>> {
+        let command = match args.subcommand()?.as_deref() {
+            Some("search") => {
+                let tags = args.values_from_str("--tag")?;
+                PuzzlesCommand::Search {
+                    query: remaining_free(args)?.join(" "),
+                    tags,
+                }
+            }
+            Some("tag") => {
+                let year = args.opt_value_from_str("--year")?;
+                let remove = args.contains("--remove");
+                PuzzlesCommand::Tag {
+                    day: args.free_from_str()?,
+                    year,
+                    remove,
+                    tags: remaining_free(args)?,
+                }
+            }
+            Some("tags") => PuzzlesCommand::Tags {
+                tags: remaining_free(args)?,
+            },
+            Some(x) => {
+                eprintln!("Unknown puzzles command: {x}");
+                process::exit(1);
+            }
+            None => {
+                eprintln!("No puzzles command specified. Use `search`, `tag` or `tags`.");
+                process::exit(1);
+            }
+        };
+
+        Ok(command)
+    }
+
+    /// Parses a comma-separated list, e.g. `1,2,4,8`.
+    fn list<T: FromStr>(value: &str) -> Result<Vec<T>, String>
+    where
+        T::Err: Display,
+    {
+        value
+            .split(',')
+            .map(str::trim)
+            .filter(|item| !item.is_empty())
+            .map(|item| item.parse().map_err(|e| format!("invalid item \"{item}\": {e}")))
+            .collect()
+    }
+
+    fn remaining_free(
+        args: &mut pico_args::Arguments,
+    ) -> Result<Vec<String>, Box<dyn std::error::Error>> {
+        let mut values = vec![];
+        while let Some(value) = args.opt_free_from_str()? {
+            values.push(value);
+        }
+        Ok(values)
+    }
 }
 
 fn main() {
@@ -102,43 +284,53 @@ fn main() {
             std::process::exit(1);
         }
         Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
+            AppArguments::All { release, junit, tap } => {
+                all::handle(release, junit.as_deref(), tap.as_deref());
+            }
+            AppArguments::Time {
+                day,
+                all,
+                store,
+                alloc,
+                comparison,
+            } => time::handle(day, all, store, alloc, comparison),
+            #[cfg(feature = "solutions")]
+            AppArguments::Serve { port } => serve::handle(port),
             AppArguments::Download { day } => download::handle(day),
             AppArguments::Read { day } => read::handle(day),
+            AppArguments::Inputs { command } => inputs::handle(command),
+            AppArguments::Puzzles { command } => puzzles::handle(command),
             AppArguments::Scaffold {
                 day,
                 download,
                 overwrite,
+                dry_run,
+                restore,
             } => {
-                scaffold::handle(day, overwrite);
-                if download {
-                    download::handle(day);
+                if restore {
+                    scaffold::restore(day, dry_run);
+                } else {
+                    scaffold::handle(day, overwrite, dry_run);
+                    if download && !dry_run {
+                        download::handle(day);
+                    }
                 }
             }
             AppArguments::Solve {
                 day,
                 release,
                 dhat,
+                alloc,
+                visualize,
+                trace,
                 submit,
-            } => solve::handle(day, release, dhat, submit),
+                inputs,
+            } => match inputs {
+                Some(dir) => solve::handle_inputs(day, &dir),
+                None => solve::handle(day, release, dhat, alloc, visualize, trace, submit),
+            },
             #[cfg(feature = "today")]
-            AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day, false);
-                        download::handle(day);
-                        read::handle(day)
-                    }
-                    None => {
-                        eprintln!(
-                            "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
-                        );
-                        process::exit(1)
-                    }
-                };
-            }
+            AppArguments::Today { wait } => today::handle(wait),
         },
     };
 }
@@ -0,0 +1,176 @@
+/// A global allocator that counts allocations, installed by [`solution!`](crate::solution) with the `alloc-stats` feature.
+/// Much cheaper than dhat, so the stats can be shown next to every timing.
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    fmt::Display,
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+/// Whether solutions count their allocations, i.e. were built with the `alloc-stats` feature.
+/// dhat installs its own allocator, so `dhat-heap` takes precedence.
+pub const ENABLED: bool = cfg!(all(feature = "alloc-stats", not(feature = "dhat-heap")));
+
+static ALLOCATIONS: AtomicU64 = AtomicU64::new(0);
+static BYTES: AtomicU64 = AtomicU64::new(0);
+static LIVE: AtomicU64 = AtomicU64::new(0);
+static PEAK: AtomicU64 = AtomicU64::new(0);
+
+/// Wraps the system allocator and counts allocations, allocated bytes and live bytes.
+pub struct CountingAlloc;
+
+unsafe impl GlobalAlloc for CountingAlloc {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        let ptr = System.alloc_zeroed(layout);
+        if !ptr.is_null() {
+            record_alloc(layout.size());
+        }
+        ptr
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout);
+        LIVE.fetch_sub(layout.size() as u64, Ordering::Relaxed);
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let new_ptr = System.realloc(ptr, layout, new_size);
+        if !new_ptr.is_null() {
+            // a reallocation counts as an allocation of the new size.
+            LIVE.fetch_sub(layout.size() as u64, Ordering::Relaxed);
+            record_alloc(new_size);
+        }
+        new_ptr
+    }
+}
+
+fn record_alloc(size: usize) {
+    ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+    BYTES.fetch_add(size as u64, Ordering::Relaxed);
+    let live = LIVE.fetch_add(size as u64, Ordering::Relaxed) + size as u64;
+    PEAK.fetch_max(live, Ordering::Relaxed);
+}
+
+/// Allocations of a part, counted from a [`Counter`].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AllocStats {
+    pub allocations: u64,
+    pub bytes: u64,
+    /// The most bytes that were live at the same time, above the live bytes at the start.
+    pub peak: u64,
+}
+
+impl Display for AllocStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} allocs, {}, peak {}",
+            self.allocations,
+            format_bytes(self.bytes),
+            format_bytes(self.peak)
+        )
+    }
+}
+
+impl AllocStats {
+    /// Parses the output of [`Display`], e.g. `12 allocs, 3.4 KiB, peak 1.2 KiB`.
+    /// The byte counts are rounded like in the output.
+    pub fn parse(s: &str) -> Option<Self> {
+        let mut fields = s.split(", ");
+        let allocations = fields.next()?.strip_suffix(" allocs")?.parse().ok()?;
+        let bytes = parse_bytes(fields.next()?)?;
+        let peak = parse_bytes(fields.next()?.strip_prefix("peak ")?)?;
+        Some(AllocStats {
+            allocations,
+            bytes,
+            peak,
+        })
+    }
+}
+
+/// Counts allocations from its creation on. Counters of parts run one after another do not overlap.
+pub struct Counter {
+    allocations: u64,
+    bytes: u64,
+    live: u64,
+}
+
+impl Counter {
+    pub fn start() -> Self {
+        let live = LIVE.load(Ordering::Relaxed);
+        PEAK.store(live, Ordering::Relaxed);
+        Counter {
+            allocations: ALLOCATIONS.load(Ordering::Relaxed),
+            bytes: BYTES.load(Ordering::Relaxed),
+            live,
+        }
+    }
+
+    pub fn stop(&self) -> AllocStats {
+        AllocStats {
+            allocations: ALLOCATIONS.load(Ordering::Relaxed) - self.allocations,
+            bytes: BYTES.load(Ordering::Relaxed) - self.bytes,
+            peak: PEAK.load(Ordering::Relaxed).saturating_sub(self.live),
+        }
+    }
+}
+
+const UNITS: [&str; 4] = ["B", "KiB", "MiB", "GiB"];
+
+#[allow(clippy::cast_precision_loss)]
+pub(crate) fn format_bytes(bytes: u64) -> String {
+    let mut value = bytes as f64;
+    let mut unit = 0;
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    if unit == 0 {
+        format!("{bytes} B")
+    } else {
+        format!("{value:.1} {}", UNITS[unit])
+    }
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub(crate) fn parse_bytes(s: &str) -> Option<u64> {
+    let (value, unit) = s.split_once(' ')?;
+    let exponent = UNITS.iter().position(|u| *u == unit)?;
+    let value: f64 = value.parse().ok()?;
+    Some((value * 1024_f64.powi(i32::try_from(exponent).ok()?)).round() as u64)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{format_bytes, AllocStats};
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(3 * 1024 * 1024), "3.0 MiB");
+    }
+
+    #[test]
+    fn parses_stats() {
+        let stats = AllocStats {
+            allocations: 12,
+            bytes: 1536,
+            peak: 100,
+        };
+        assert_eq!(stats.to_string(), "12 allocs, 1.5 KiB, peak 100 B");
+        assert_eq!(AllocStats::parse(&stats.to_string()), Some(stats));
+        assert_eq!(AllocStats::parse("12 allocs"), None);
+    }
+}
@@ -0,0 +1,133 @@
+/// Build configurations that solutions are run with, e.g. by `cargo time --profiles release,native`.
+use std::{env, path::PathBuf, process::Command};
+
+/// `RUSTFLAGS` added by profiles ending in `native`.
+const NATIVE_RUSTFLAGS: &str = "-C target-cpu=native";
+
+/// A cargo profile plus extra `RUSTFLAGS`, optionally built into a target dir of its own.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BuildProfile {
+    /// The name the profile was selected with, e.g. `release-native`.
+    pub name: String,
+    /// The cargo profile, e.g. `release` or `release-lto`.
+    pub cargo_profile: String,
+    pub rustflags: Option<String>,
+    pub target_dir: Option<PathBuf>,
+    pub features: Vec<String>,
+    /// Size of rayon's global thread pool, set with `RAYON_NUM_THREADS`.
+    pub threads: Option<usize>,
+}
+
+impl BuildProfile {
+    pub fn dev() -> Self {
+        Self::plain("dev")
+    }
+
+    pub fn release() -> Self {
+        Self::plain("release")
+    }
+
+    fn plain(cargo_profile: &str) -> Self {
+        BuildProfile {
+            name: cargo_profile.into(),
+            cargo_profile: cargo_profile.into(),
+            rustflags: None,
+            target_dir: None,
+            features: vec![],
+            threads: None,
+        }
+    }
+
+    /// Selects a profile for a comparison. Names are cargo profiles, e.g. `release-lto`.
+    /// A `native` suffix builds for the current CPU, `native` alone is short for `release-native`.
+    /// Every profile is built into `target/profiles/<name>`, so switching between them does not rebuild.
+    pub fn parse(name: &str) -> Self {
+        let (cargo_profile, rustflags) = match name {
+            "native" => ("release", Some(NATIVE_RUSTFLAGS.to_string())),
+            name => match name.strip_suffix("-native") {
+                Some(profile) => (profile, Some(NATIVE_RUSTFLAGS.to_string())),
+                None => (name, None),
+            },
+        };
+
+        let target_dir = env::var_os("CARGO_TARGET_DIR")
+            .map_or_else(|| PathBuf::from("target"), PathBuf::from)
+            .join("profiles")
+            .join(name);
+
+        BuildProfile {
+            name: name.into(),
+            cargo_profile: cargo_profile.into(),
+            rustflags,
+            target_dir: Some(target_dir),
+            features: vec![],
+            threads: None,
+        }
+    }
+
+    /// Enables a cargo feature, e.g. `alloc-stats`.
+    #[must_use]
+    pub fn with_feature(mut self, feature: &str) -> Self {
+        self.features.push(feature.into());
+        self
+    }
+
+    /// Runs solutions with `threads` rayon threads.
+    #[must_use]
+    pub fn with_threads(mut self, threads: usize) -> Self {
+        self.threads = Some(threads);
+        self
+    }
+
+    /// Adds the profile to a `cargo build` or `cargo run` command.
+    pub fn configure(&self, cmd: &mut Command) {
+        cmd.args(["--profile", &self.cargo_profile]);
+
+        if !self.features.is_empty() {
+            cmd.args(["--features", &self.features.join(",")]);
+        }
+
+        if let Some(rustflags) = &self.rustflags {
+            // keep flags that are set already, e.g. for linking.
+            let rustflags = match env::var("RUSTFLAGS") {
+                Ok(existing) if !existing.trim().is_empty() => format!("{existing} {rustflags}"),
+                _ => rustflags.clone(),
+            };
+            cmd.env("RUSTFLAGS", rustflags);
+        }
+
+        if let Some(target_dir) = &self.target_dir {
+            cmd.env("CARGO_TARGET_DIR", target_dir);
+        }
+
+        if let Some(threads) = self.threads {
+            cmd.env("RAYON_NUM_THREADS", threads.to_string());
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{BuildProfile, NATIVE_RUSTFLAGS};
+
+    #[test]
+    fn parses_profiles() {
+        let profile = BuildProfile::parse("release-lto");
+        assert_eq!(profile.cargo_profile, "release-lto");
+        assert_eq!(profile.rustflags, None);
+        assert!(profile.target_dir.unwrap().ends_with("profiles/release-lto"));
+
+        let profile = BuildProfile::parse("native");
+        assert_eq!(profile.cargo_profile, "release");
+        assert_eq!(profile.rustflags.as_deref(), Some(NATIVE_RUSTFLAGS));
+
+        let profile = BuildProfile::parse("release-lto-native");
+        assert_eq!(profile.name, "release-lto-native");
+        assert_eq!(profile.cargo_profile, "release-lto");
+        assert_eq!(profile.rustflags.as_deref(), Some(NATIVE_RUSTFLAGS));
+
+        assert_eq!(BuildProfile::release().target_dir, None);
+    }
+}
@@ -1,5 +1,38 @@
-use crate::template::{all_days, run_multi::run_multi};
+use std::{path::Path, process};
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+use crate::template::{all_days, build::BuildProfile, config, run_multi::run_multi};
+
+/// Runs all days and exits with a non-zero code if a part panicked, failed to build, timed out or gave a wrong answer.
+pub fn handle(is_release: bool, junit: Option<&Path>, tap: Option<&Path>) {
+    let profile = if is_release || config::get().release {
+        BuildProfile::release()
+    } else {
+        BuildProfile::dev()
+    };
+    let (report, _) = run_multi(&all_days().collect(), &profile, false);
+    let mut failed = report.is_failure();
+
+    if let Some(path) = junit {
+        match report.write_junit(path) {
+            Ok(()) => println!("Wrote JUnit report to \"{}\".", path.display()),
+            Err(e) => {
+                eprintln!("Failed to write JUnit report to \"{}\": {e}", path.display());
+                failed = true;
+            }
+        }
+    }
+
+    if let Some(path) = tap {
+        match report.write_tap(path) {
+            Ok(()) => println!("Wrote TAP report to \"{}\".", path.display()),
+            Err(e) => {
+                eprintln!("Failed to write TAP report to \"{}\": {e}", path.display());
+                failed = true;
+            }
+        }
+    }
+
+    if failed {
+        process::exit(1);
+    }
 }
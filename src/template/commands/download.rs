@@ -1,5 +1,11 @@
-use crate::template::{aoc_cli, Day};
-use std::process;
+use crate::template::{
+    aoc_cli::{self, AocCommandError},
+    config,
+    crypto::{self, CryptoError, Key},
+    manifest::{Kind, Manifest},
+    Day,
+};
+use std::{fs, process};
 
 pub fn handle(day: Day) {
     if aoc_cli::check().is_err() {
@@ -7,8 +13,55 @@ pub fn handle(day: Day) {
         process::exit(1);
     }
 
-    if let Err(e) = aoc_cli::download(day) {
+    if let Err(e) = fetch(day) {
         eprintln!("failed to call aoc-cli: {e}");
         process::exit(1);
     };
 }
+
+/// Downloads the input and puzzle of a day via aoc-cli, then records and encrypts them.
+pub fn fetch(day: Day) -> Result<(), AocCommandError> {
+    aoc_cli::download(day)?;
+    record(day);
+    encrypt(day);
+    Ok(())
+}
+
+/// Encrypts the downloaded input if an input key is set up.
+fn encrypt(day: Day) {
+    let key = match Key::load() {
+        Ok(Some(key)) => key,
+        Ok(None) => return,
+        Err(e) => {
+            eprintln!("Failed to load input key, the input is stored unencrypted: {e}");
+            return;
+        }
+    };
+
+    let path = config::get().input_path(day);
+    let result = fs::read(&path).map_err(CryptoError::from).and_then(|contents| {
+        if !crypto::is_encrypted(&contents) {
+            fs::write(&path, crypto::encrypt(&key, &contents)?)?;
+        }
+        Ok(())
+    });
+
+    match result {
+        Ok(()) => println!("🔒 Encrypted input \"{}\".", path.display()),
+        Err(e) => eprintln!("Failed to encrypt input, it is stored unencrypted: {e}"),
+    }
+}
+
+/// Records checksums of the downloaded files, so later edits can be detected.
+fn record(day: Day) {
+    let result = Manifest::read_from_file().and_then(|mut manifest| {
+        for kind in [Kind::Input, Kind::Puzzle] {
+            manifest.record(kind, day)?;
+        }
+        manifest.store_file().map_err(|e| e.to_string())
+    });
+
+    if let Err(e) = result {
+        eprintln!("Failed to record checksums of the downloaded files: {e}");
+    }
+}
@@ -0,0 +1,314 @@
+use std::{
+    collections::BTreeSet,
+    fs, io,
+    path::{Path, PathBuf},
+    process,
+};
+
+use crate::template::{
+    commands::download,
+    config,
+    crypto::{self, Key, KEY_ENV_VAR},
+    manifest::{Manifest, Status},
+    Day,
+};
+
+pub enum InputsCommand {
+    /// Check downloaded inputs and puzzles against their recorded checksums.
+    Verify { day: Option<Day> },
+    /// Download a day again. Without a day, downloads every day whose files do not match.
+    Restore { day: Option<Day> },
+    /// Encrypt all plaintext inputs with the input key.
+    Encrypt,
+    /// Decrypt all encrypted inputs for local editing.
+    Decrypt,
+    /// Generate a new input key and re-encrypt all inputs with it.
+    Rekey,
+}
+
+pub fn handle(command: InputsCommand) {
+    match command {
+        InputsCommand::Verify { day } => verify(&read_manifest(), day),
+        InputsCommand::Restore { day } => restore(&read_manifest(), day),
+        InputsCommand::Encrypt => encrypt(),
+        InputsCommand::Decrypt => decrypt(),
+        InputsCommand::Rekey => rekey(),
+    }
+}
+
+fn read_manifest() -> Manifest {
+    Manifest::read_from_file().unwrap_or_else(|e| {
+        eprintln!("Failed to read manifest: {e}");
+        process::exit(1);
+    })
+}
+
+fn verify(manifest: &Manifest, day: Option<Day>) {
+    let tracked = manifest.tracked(day);
+
+    if tracked.is_empty() {
+        println!(
+            "No downloaded files recorded in \"{}\". Files are recorded when downloaded with `cargo download`.",
+            config::get().manifest_path.display()
+        );
+        return;
+    }
+
+    let mut failed = 0;
+
+    for (kind, day) in tracked {
+        let path = kind.path(day);
+        match manifest.check(kind, day) {
+            Status::Ok | Status::Untracked => println!("✔ {kind} {}", path.display()),
+            Status::Modified(size) => {
+                failed += 1;
+                let recorded = manifest.get(kind, day).map_or(0, |e| e.size);
+                println!(
+                    "✖ {kind} {} was modified ({size} bytes, downloaded {recorded} bytes)",
+                    path.display()
+                );
+            }
+            Status::Missing => {
+                failed += 1;
+                println!("✖ {kind} {} is missing", path.display());
+            }
+            Status::Unreadable(e) => {
+                failed += 1;
+                println!("✖ {kind} {} could not be read: {e}", path.display());
+            }
+        }
+    }
+
+    if failed > 0 {
+        eprintln!();
+        eprintln!(
+            "{failed} file(s) do not match. Run `cargo inputs restore` to download them again."
+        );
+        process::exit(1);
+    }
+}
+
+fn restore(manifest: &Manifest, day: Option<Day>) {
+    let days: BTreeSet<Day> = match day {
+        Some(day) => BTreeSet::from([day]),
+        None => manifest
+            .tracked(None)
+            .into_iter()
+            .filter(|(kind, day)| !matches!(manifest.check(*kind, *day), Status::Ok))
+            .map(|(_, day)| day)
+            .collect(),
+    };
+
+    if days.is_empty() {
+        println!("🎄 All downloaded files match, nothing to restore.");
+        return;
+    }
+
+    for day in days {
+        download::handle(day);
+    }
+}
+
+fn encrypt() {
+    let key = load_key().unwrap_or_else(|| {
+        eprintln!("No input key found. Run `cargo inputs rekey` to generate one.");
+        process::exit(1);
+    });
+
+    let mut count = 0;
+    for (path, contents) in read_inputs() {
+        if crypto::is_encrypted(&contents) {
+            continue;
+        }
+        write(&path, encrypt_or_exit(&key, &contents));
+        println!("🔒 {}", path.display());
+        count += 1;
+    }
+
+    println!("Encrypted {count} input(s).");
+}
+
+fn decrypt() {
+    let mut count = 0;
+    for (path, plain) in decrypt_inputs() {
+        write(&path, plain);
+        println!("🔓 {}", path.display());
+        count += 1;
+    }
+
+    println!("Decrypted {count} input(s). Run `cargo inputs encrypt` before committing them.");
+}
+
+fn rekey() {
+    // decrypt everything before touching any file, so a wrong key leaves all inputs intact.
+    let inputs: Vec<(PathBuf, Vec<u8>)> = read_inputs()
+        .into_iter()
+        .map(|(path, contents)| match crypto::plain(contents) {
+            Ok(plain) => (path, plain),
+            Err(e) => {
+                eprintln!("Failed to decrypt \"{}\": {e}", path.display());
+                process::exit(1);
+            }
+        })
+        .collect();
+
+    let key = Key::generate().unwrap_or_else(|e| {
+        eprintln!("Failed to generate input key: {e}");
+        process::exit(1);
+    });
+    let encrypted: Vec<(&PathBuf, String)> = inputs
+        .iter()
+        .map(|(path, plain)| (path, encrypt_or_exit(&key, plain)))
+        .collect();
+
+    // stage every file before replacing any, so a failed write leaves the old inputs and key intact.
+    let mut staged: Vec<(&PathBuf, PathBuf)> = Vec::with_capacity(encrypted.len());
+    for (path, contents) in encrypted {
+        let tmp = path.with_extension("txt.tmp");
+        if let Err(e) = fs::write(&tmp, contents) {
+            eprintln!("Failed to write \"{}\": {e}", tmp.display());
+            let _ = fs::remove_file(&tmp);
+            for (_, tmp) in &staged {
+                let _ = fs::remove_file(tmp);
+            }
+            process::exit(1);
+        }
+        staged.push((path, tmp));
+    }
+
+    for (path, tmp) in &staged {
+        if let Err(e) = fs::rename(tmp, path) {
+            eprintln!("Failed to replace \"{}\": {e}", path.display());
+            eprintln!("Inputs may be encrypted with the new key: {}", key.to_hex());
+            process::exit(1);
+        }
+    }
+
+    if let Err(e) = key.store_file() {
+        eprintln!("Failed to store input key: {e}");
+        eprintln!("Inputs are encrypted with the new key, store it manually: {}", key.to_hex());
+        process::exit(1);
+    }
+
+    println!(
+        "🔑 Stored a new input key in \"{}\" and encrypted {} input(s).",
+        config::get().input_key_path.display(),
+        inputs.len()
+    );
+    println!("   Key: {}", key.to_hex());
+
+    if std::env::var(KEY_ENV_VAR).is_ok_and(|v| !v.trim().is_empty()) {
+        println!("   `{KEY_ENV_VAR}` is set and takes precedence over the key file, update it and any CI secrets to the new key.");
+    }
+}
+
+fn encrypt_or_exit(key: &Key, plain: &[u8]) -> String {
+    crypto::encrypt(key, plain).unwrap_or_else(|e| {
+        eprintln!("Failed to encrypt inputs: {e}");
+        process::exit(1);
+    })
+}
+
+fn load_key() -> Option<Key> {
+    Key::load().unwrap_or_else(|e| {
+        eprintln!("Failed to load input key: {e}");
+        process::exit(1);
+    })
+}
+
+/// Decrypted contents of all encrypted inputs.
+fn decrypt_inputs() -> Vec<(PathBuf, Vec<u8>)> {
+    let inputs: Vec<_> = read_inputs()
+        .into_iter()
+        .filter(|(_, contents)| crypto::is_encrypted(contents))
+        .collect();
+
+    if inputs.is_empty() {
+        return inputs;
+    }
+
+    let key = load_key().unwrap_or_else(|| {
+        eprintln!(
+            "Failed to decrypt inputs: {}",
+            crypto::CryptoError::MissingKey
+        );
+        process::exit(1);
+    });
+
+    inputs
+        .into_iter()
+        .map(|(path, contents)| match crypto::decrypt(&key, &contents) {
+            Ok(plain) => (path, plain),
+            Err(e) => {
+                eprintln!("Failed to decrypt \"{}\": {e}", path.display());
+                process::exit(1);
+            }
+        })
+        .collect()
+}
+
+/// Paths and raw contents of all `.txt` files in the inputs directory and its subdirectories,
+/// e.g. the inputs of a team in `data/inputs/06/`.
+fn read_inputs() -> Vec<(PathBuf, Vec<u8>)> {
+    let dir = &config::get().inputs_dir;
+    let result = list_txt_files(dir).and_then(|paths| {
+        paths
+            .into_iter()
+            .map(|path| fs::read(&path).map(|contents| (path, contents)))
+            .collect()
+    });
+
+    match result {
+        Ok(inputs) => inputs,
+        Err(e) if e.kind() == io::ErrorKind::NotFound => vec![],
+        Err(e) => {
+            eprintln!("Failed to read inputs from \"{}\": {e}", dir.display());
+            process::exit(1);
+        }
+    }
+}
+
+/// Paths of all `.txt` files below `dir`, sorted.
+fn list_txt_files(dir: &Path) -> io::Result<Vec<PathBuf>> {
+    let mut paths = vec![];
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            paths.extend(list_txt_files(&path)?);
+        } else if path.extension().is_some_and(|ext| ext == "txt") {
+            paths.push(path);
+        }
+    }
+    paths.sort();
+    Ok(paths)
+}
+
+fn write(path: &Path, contents: impl AsRef<[u8]>) {
+    if let Err(e) = fs::write(path, contents) {
+        eprintln!("Failed to write \"{}\": {e}", path.display());
+        process::exit(1);
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::fs;
+
+    use super::list_txt_files;
+
+    #[test]
+    fn lists_inputs_in_subdirectories() {
+        let dir = std::env::temp_dir().join(format!("aoc-inputs-{}", std::process::id()));
+        fs::create_dir_all(dir.join("06")).unwrap();
+        fs::write(dir.join("01.txt"), "1").unwrap();
+        fs::write(dir.join("06/alice.txt"), "2").unwrap();
+        fs::write(dir.join("06/alice.answers"), "3").unwrap();
+
+        let paths = list_txt_files(&dir).unwrap();
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(paths, [dir.join("01.txt"), dir.join("06/alice.txt")]);
+    }
+}
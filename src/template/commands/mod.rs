@@ -1,6 +1,12 @@
 pub mod all;
 pub mod download;
+pub mod inputs;
+pub mod puzzles;
 pub mod read;
 pub mod scaffold;
+#[cfg(feature = "solutions")]
+pub mod serve;
 pub mod solve;
 pub mod time;
+#[cfg(feature = "today")]
+pub mod today;
@@ -0,0 +1,119 @@
+use std::process;
+
+use crate::template::{
+    config,
+    puzzles::{self, PuzzleId, TagIndex},
+    terminal, Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
+
+pub enum PuzzlesCommand {
+    /// Full-text search in the downloaded descriptions, optionally limited to puzzles with tags.
+    Search { query: String, tags: Vec<String> },
+    /// Add tags to (or remove them from) a puzzle.
+    Tag {
+        day: Day,
+        year: Option<u16>,
+        tags: Vec<String>,
+        remove: bool,
+    },
+    /// List puzzles by tag. With tags given, lists the puzzles that have all of them.
+    Tags { tags: Vec<String> },
+}
+
+pub fn handle(command: PuzzlesCommand) {
+    let mut index = TagIndex::read_from_file().unwrap_or_else(|e| {
+        eprintln!("Failed to read tag index: {e}");
+        process::exit(1);
+    });
+
+    match command {
+        PuzzlesCommand::Search { query, tags } => search(&query, &tags, &index),
+        PuzzlesCommand::Tag {
+            day,
+            year,
+            tags,
+            remove,
+        } => {
+            let id = PuzzleId {
+                year: year.or_else(|| config::get().year()),
+                day,
+            };
+
+            if remove {
+                index.remove(id, &tags);
+            } else {
+                index.add(id, &tags);
+            }
+
+            if let Err(e) = index.store_file() {
+                eprintln!("Failed to store tag index: {e}");
+                process::exit(1);
+            }
+
+            let tags = index
+                .get(id)
+                .map(|t| t.iter().cloned().collect::<Vec<_>>().join(", "))
+                .unwrap_or_default();
+            println!("🎄 Tags of {id}: {tags}");
+        }
+        PuzzlesCommand::Tags { tags } => {
+            if tags.is_empty() {
+                for (tag, ids) in index.by_tag() {
+                    let ids = ids.iter().map(ToString::to_string).collect::<Vec<_>>();
+                    println!("{ANSI_BOLD}{tag}{ANSI_RESET}: {}", ids.join(", "));
+                }
+            } else {
+                let titles = titles();
+                for id in index.find(&tags) {
+                    print_puzzle(id, titles.iter().find(|(i, _)| *i == id).map(|(_, t)| t));
+                }
+            }
+        }
+    }
+}
+
+fn search(query: &str, tags: &[String], index: &TagIndex) {
+    let candidates = index.find(tags);
+
+    let puzzles: Vec<(PuzzleId, String)> = puzzles::discover()
+        .into_iter()
+        .filter(|p| tags.is_empty() || candidates.contains(&p.id))
+        .filter_map(|p| Some((p.id, p.description().ok()?)))
+        .collect();
+
+    let results = puzzles::search(&puzzles, query, terminal::is_interactive());
+
+    if results.is_empty() {
+        println!("No puzzles found for \"{query}\".");
+        return;
+    }
+
+    for result in results {
+        let title = result.title.map(|t| format!(": {t}")).unwrap_or_default();
+        let tags = index
+            .get(result.id)
+            .map(|t| format!(" [{}]", t.iter().cloned().collect::<Vec<_>>().join(", ")))
+            .unwrap_or_default();
+        println!(
+            "{ANSI_BOLD}{}{title}{ANSI_RESET}{tags} {ANSI_ITALIC}({} matches){ANSI_RESET}",
+            result.id, result.score
+        );
+        for snippet in result.snippets {
+            println!("    {snippet}");
+        }
+    }
+}
+
+fn titles() -> Vec<(PuzzleId, String)> {
+    puzzles::discover()
+        .into_iter()
+        .filter_map(|p| Some((p.id, puzzles::title(&p.description().ok()?)?)))
+        .collect()
+}
+
+fn print_puzzle(id: PuzzleId, title: Option<&String>) {
+    match title {
+        Some(title) => println!("{id}: {title}"),
+        None => println!("{id}"),
+    }
+}
@@ -1,10 +1,23 @@
-use std::process;
+use std::{fs, process};
 
-use crate::template::{aoc_cli, Day};
+use crate::template::{aoc_cli, config, markdown, terminal, Day};
 
+/// Renders the stored puzzle description. If it has not been downloaded yet, reads it via aoc-cli.
 pub fn handle(day: Day) {
+    let puzzle_path = config::get().puzzle_path(day);
+
+    if let Ok(description) = fs::read_to_string(&puzzle_path) {
+        let rendered =
+            markdown::render(&description, terminal::width(), terminal::is_interactive());
+        terminal::page(&rendered);
+        return;
+    }
+
     if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+        eprintln!(
+            "No stored puzzle found at \"{}\" and command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.",
+            puzzle_path.display()
+        );
         process::exit(1);
     }
 
@@ -1,79 +1,501 @@
 use std::{
-    fs::{File, OpenOptions},
-    io::Write,
+    fs, io,
+    path::{Component, Path, PathBuf},
     process,
+    time::{SystemTime, UNIX_EPOCH},
 };
 
-use crate::template::Day;
+use crate::template::{config, Day};
 
 const MODULE_TEMPLATE: &str =
     include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
 
-fn safe_create_file(path: &str, overwrite: bool) -> Result<File, std::io::Error> {
-    let mut file = OpenOptions::new();
-    if overwrite {
-        file.create(true);
-    } else {
-        file.create_new(true);
+/// The binary only runs the solution from the library module.
+const BIN_TEMPLATE: &str = "use advent_of_code::days::%MODULE%::*;
+
+advent_of_code::solution!(%DAY_NUMBER%);
+";
+
+const DAYS_MODULE_PATH: &str = "src/days/mod.rs";
+
+fn is_registered(module: &str) -> Result<bool, io::Error> {
+    let declaration = format!("pub mod {module};");
+    Ok(fs::read_to_string(DAYS_MODULE_PATH)?
+        .lines()
+        .any(|line| line == declaration))
+}
+
+/// Adds `pub mod dayNN;` and its `solutions!` entry to the days module, keeping both sorted.
+fn register_module(module: &str) -> Result<bool, io::Error> {
+    if is_registered(module)? {
+        return Ok(false);
     }
-    file.truncate(true).write(true).open(path)
+
+    let contents = fs::read_to_string(DAYS_MODULE_PATH)?;
+    fs::write(DAYS_MODULE_PATH, with_module(&contents, module))?;
+    Ok(true)
 }
 
-fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new()
-        .write(true)
-        .create(true)
-        .truncate(true)
-        .open(path)
+/// Returns the days module with `module` declared and registered.
+fn with_module(contents: &str, module: &str) -> String {
+    let declaration = format!("pub mod {module};");
+
+    let mut lines: Vec<&str> = contents.lines().collect();
+    let registry = lines
+        .iter()
+        .position(|line| line.contains("solutions!("))
+        .unwrap_or(lines.len());
+
+    insert_sorted(
+        &mut lines,
+        &declaration,
+        |line| line.starts_with("pub mod day"),
+        registry,
+    );
+
+    let modules: Vec<&str> = lines
+        .iter()
+        .filter_map(|line| line.strip_prefix("pub mod ")?.strip_suffix(';'))
+        .filter(|name| name.starts_with("day"))
+        .collect();
+    let registry_lines = render_registry(&modules);
+
+    // the registry is rewritten as a whole, so it stays `cargo fmt` clean as it grows.
+    if let Some(start) = lines.iter().position(|line| line.contains("solutions!(")) {
+        let end = lines[start..]
+            .iter()
+            .position(|line| line.trim_end().ends_with(");"))
+            .map_or(start, |offset| start + offset);
+        lines.splice(start..=end, registry_lines.lines());
+    }
+
+    lines.join("\n") + "\n"
 }
 
-pub fn handle(day: Day, overwrite: bool) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+/// Renders the `solutions!` invocation the way rustfmt lays it out.
+fn render_registry(modules: &[&str]) -> String {
+    const MAX_WIDTH: usize = 100;
+    const INDENT: &str = "  ";
 
-    let mut file = match safe_create_file(&module_path, overwrite) {
-        Ok(file) => file,
-        Err(e) => {
-            eprintln!("Failed to create module file: {e}");
-            process::exit(1);
+    let entries: Vec<String> = modules.iter().map(|module| format!("{module},")).collect();
+    let single_line = format!("crate::solutions!({});", entries.join(" "));
+    if single_line.len() <= MAX_WIDTH {
+        return single_line;
+    }
+
+    let mut rendered = String::from("crate::solutions!(\n");
+    let mut line = String::new();
+    for entry in entries {
+        if !line.is_empty() && INDENT.len() + line.len() + 1 + entry.len() > MAX_WIDTH {
+            rendered.push_str(&format!("{INDENT}{line}\n"));
+            line.clear();
         }
-    };
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(&entry);
+    }
+    rendered.push_str(&format!("{INDENT}{line}\n);"));
+    rendered
+}
+
+fn insert_sorted<'a>(
+    lines: &mut Vec<&'a str>,
+    line: &'a str,
+    is_sibling: impl Fn(&str) -> bool,
+    fallback: usize,
+) {
+    let position = lines
+        .iter()
+        .position(|l| is_sibling(l) && *l > line)
+        .or_else(|| lines.iter().rposition(|l| is_sibling(l)).map(|i| i + 1))
+        .unwrap_or(fallback);
+    lines.insert(position, line);
+}
+
+/// A step of scaffolding a day. All steps are planned before anything is written, so `--dry-run` can print them.
+#[derive(Debug, PartialEq)]
+enum Action {
+    /// Writes a file that does not exist yet.
+    Create {
+        path: PathBuf,
+        kind: &'static str,
+        contents: String,
+    },
+    /// Backs up an existing file, then replaces it.
+    Overwrite {
+        path: PathBuf,
+        kind: &'static str,
+        contents: String,
+    },
+    /// Leaves an existing file as it is.
+    Keep {
+        path: PathBuf,
+        kind: &'static str,
+        reason: &'static str,
+    },
+    /// Adds the module to the days module.
+    Register { module: String },
+}
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
-        Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
+impl Action {
+    fn describe(&self, dry_run: bool) -> String {
+        let verb = |done: &'static str, planned: &'static str| if dry_run { planned } else { done };
+        match self {
+            Action::Create {
+                path,
+                kind,
+                contents,
+            } => {
+                let empty = if contents.is_empty() { "empty " } else { "" };
+                format!(
+                    "{} {empty}{kind} \"{}\"",
+                    verb("Created", "Would create"),
+                    path.display()
+                )
+            }
+            Action::Overwrite { path, kind, .. } => format!(
+                "{} {kind} \"{}\"",
+                verb("Backed up and overwrote", "Would back up and overwrite"),
+                path.display()
+            ),
+            Action::Keep { path, kind, reason } => format!(
+                "{} {kind} \"{}\": {reason}",
+                verb("Kept", "Would keep"),
+                path.display()
+            ),
+            Action::Register { module } => format!(
+                "{} module \"{module}\" to \"{DAYS_MODULE_PATH}\"",
+                verb("Added", "Would add")
+            ),
         }
-        Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
-            process::exit(1);
+    }
+
+    /// Runs the action, backing up overwritten files to `backup`, which is created on first use.
+    fn run(&self, backup: &mut Option<Backup>) -> Result<(), io::Error> {
+        match self {
+            Action::Create { path, contents, .. } => {
+                if let Some(parent) = path.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                fs::write(path, contents)
+            }
+            Action::Overwrite { path, contents, .. } => {
+                let backup = match backup {
+                    Some(backup) => backup,
+                    None => backup.insert(Backup::create(&config::get().backups_dir)?),
+                };
+                backup.save(path)?;
+                fs::write(path, contents)
+            }
+            Action::Keep { .. } => Ok(()),
+            Action::Register { module } => register_module(module).map(|_| ()),
         }
     }
+}
+
+/// Plans writing a generated file. Existing files are only replaced with `overwrite`.
+fn plan_file(path: PathBuf, kind: &'static str, contents: String, overwrite: bool) -> Action {
+    match fs::read_to_string(&path) {
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Action::Create {
+            path,
+            kind,
+            contents,
+        },
+        Ok(existing) if existing == contents => Action::Keep {
+            path,
+            kind,
+            reason: "up to date",
+        },
+        _ if overwrite => Action::Overwrite {
+            path,
+            kind,
+            contents,
+        },
+        _ => Action::Keep {
+            path,
+            kind,
+            reason: "already exists, pass `--overwrite` to replace it",
+        },
+    }
+}
+
+/// Plans creating an empty input or example file. Existing data files are never replaced.
+fn plan_data_file(path: PathBuf, kind: &'static str) -> Action {
+    match fs::metadata(&path) {
+        Ok(metadata) if metadata.len() > 0 => Action::Keep {
+            path,
+            kind,
+            reason: "has data",
+        },
+        Ok(_) => Action::Keep {
+            path,
+            kind,
+            reason: "already exists",
+        },
+        Err(_) => Action::Create {
+            path,
+            kind,
+            contents: String::new(),
+        },
+    }
+}
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
+/// A directory in `paths.backups`, named after the time it was created, e.g. `20241201-050012`.
+/// Files are stored under their path relative to the project, e.g. `src/days/day01.rs`.
+struct Backup {
+    dir: PathBuf,
+}
+
+impl Backup {
+    fn create(root: &Path) -> Result<Self, io::Error> {
+        let secs = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |d| d.as_secs());
+        let name = format_timestamp(secs);
+
+        // two backups in the same second get a suffix.
+        let mut dir = root.join(&name);
+        let mut n = 1;
+        while dir.exists() {
+            n += 1;
+            dir = root.join(format!("{name}-{n}"));
         }
-        Err(e) => {
-            eprintln!("Failed to create input file: {e}");
-            process::exit(1);
+
+        fs::create_dir_all(&dir)?;
+        Ok(Backup { dir })
+    }
+
+    fn path_of(&self, path: &Path) -> PathBuf {
+        // absolute paths are stored relative to the root.
+        let relative: PathBuf = path
+            .components()
+            .filter(|c| matches!(c, Component::Normal(_)))
+            .collect();
+        self.dir.join(relative)
+    }
+
+    fn save(&self, path: &Path) -> Result<(), io::Error> {
+        let target = self.path_of(path);
+        if let Some(parent) = target.parent() {
+            fs::create_dir_all(parent)?;
         }
+        fs::copy(path, target).map(|_| ())
+    }
+
+    /// The most recent backup in `root` that contains any of `paths`.
+    fn latest(root: &Path, paths: &[PathBuf]) -> Option<Self> {
+        let mut dirs: Vec<PathBuf> = fs::read_dir(root)
+            .ok()?
+            .filter_map(|entry| Some(entry.ok()?.path()))
+            .filter(|path| path.is_dir())
+            .collect();
+        // the names sort by time.
+        dirs.sort_unstable();
+
+        dirs.into_iter()
+            .rev()
+            .map(|dir| Backup { dir })
+            .find(|backup| paths.iter().any(|path| backup.path_of(path).is_file()))
     }
+}
+
+/// Formats seconds since the unix epoch as `YYYYMMDD-HHMMSS` in UTC.
+fn format_timestamp(secs: u64) -> String {
+    let (days, time) = (secs / 86_400, secs % 86_400);
+
+    // days to civil date, see http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z % 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+
+    format!(
+        "{year:04}{month:02}{day:02}-{:02}{:02}{:02}",
+        time / 3600,
+        time / 60 % 60,
+        time % 60
+    )
+}
+
+fn render(template: &str, day: Day, module: &str) -> String {
+    template
+        .replace("%DAY_NUMBER%", &day.into_inner().to_string())
+        .replace("%MODULE%", module)
+}
+
+fn module_paths(module: &str, day: Day) -> [PathBuf; 2] {
+    [
+        PathBuf::from(format!("src/days/{module}.rs")),
+        PathBuf::from(format!("src/bin/{day}.rs")),
+    ]
+}
+
+/// Prints the actions, then runs them unless `dry_run` is set.
+fn execute(actions: &[Action], dry_run: bool) -> Option<Backup> {
+    let mut backup = None;
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
+    for action in actions {
+        if !dry_run {
+            if let Err(e) = action.run(&mut backup) {
+                let action = action.describe(true).replacen("Would ", "", 1);
+                eprintln!("Failed to {action}: {e}");
+                process::exit(1);
+            }
         }
+        println!("{}", action.describe(dry_run));
+    }
+
+    backup
+}
+
+/// Scaffolds a day. Running it again is safe: existing module files are kept unless `overwrite` is set,
+/// in which case they are backed up first, and input and example files are never replaced.
+pub fn handle(day: Day, overwrite: bool, dry_run: bool) {
+    let config = config::get();
+    let module = format!("day{day}");
+    let [module_path, bin_path] = module_paths(&module, day);
+
+    let mut actions = vec![
+        plan_file(
+            module_path,
+            "module file",
+            render(MODULE_TEMPLATE, day, &module),
+            overwrite,
+        ),
+        plan_file(
+            bin_path,
+            "binary file",
+            render(BIN_TEMPLATE, day, &module),
+            overwrite,
+        ),
+    ];
+
+    match is_registered(&module) {
+        Ok(true) => {}
+        Ok(false) => actions.push(Action::Register {
+            module: module.clone(),
+        }),
         Err(e) => {
-            eprintln!("Failed to create example file: {e}");
+            eprintln!("Failed to read \"{DAYS_MODULE_PATH}\": {e}");
             process::exit(1);
         }
     }
 
+    actions.push(plan_data_file(config.input_path(day), "input file"));
+    actions.push(plan_data_file(config.example_path(day), "example file"));
+
+    let backup = execute(&actions, dry_run);
+
     println!("---");
+    if dry_run {
+        println!("🎄 Nothing was written, run without `--dry-run` to scaffold day {day}.");
+        return;
+    }
+    if let Some(backup) = backup {
+        println!(
+            "🎄 Backed up the previous files to \"{}\". Type `cargo scaffold {day} --restore` to restore them.",
+            backup.dir.display()
+        );
+    }
     println!("🎄 Type `cargo solve {day}` to run your solution.");
 }
+
+/// Restores the module files of a day from the latest backup that has them.
+/// The current files are backed up as well, so restoring again undoes the restore.
+pub fn restore(day: Day, dry_run: bool) {
+    let module = format!("day{day}");
+    let paths = module_paths(&module, day);
+
+    let Some(backup) = Backup::latest(&config::get().backups_dir, &paths) else {
+        eprintln!("No backup of day {day} found.");
+        process::exit(1);
+    };
+
+    println!("Restoring from \"{}\".", backup.dir.display());
+
+    let actions: Vec<Action> = paths
+        .into_iter()
+        .zip(["module file", "binary file"])
+        .filter_map(|(path, kind)| {
+            let contents = fs::read_to_string(backup.path_of(&path)).ok()?;
+            Some(plan_file(path, kind, contents, true))
+        })
+        .collect();
+
+    execute(&actions, dry_run);
+
+    if dry_run {
+        println!("---");
+        println!("🎄 Nothing was written, run without `--dry-run` to restore day {day}.");
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{env, fs};
+
+    use super::{format_timestamp, plan_data_file, plan_file, with_module, Action};
+
+    #[test]
+    fn formats_timestamps() {
+        assert_eq!(format_timestamp(0), "19700101-000000");
+        assert_eq!(format_timestamp(1_733_029_212), "20241201-050012");
+        assert_eq!(format_timestamp(951_782_400), "20000229-000000");
+    }
+
+    #[test]
+    fn never_plans_to_replace_data() {
+        let dir = env::temp_dir().join(format!("aoc-scaffold-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let input = dir.join("01.txt");
+        let module = dir.join("day01.rs");
+
+        assert!(matches!(plan_data_file(input.clone(), "input file"), Action::Create { .. }));
+        fs::write(&input, "1 2 3").unwrap();
+        assert!(matches!(
+            plan_data_file(input.clone(), "input file"),
+            Action::Keep { reason: "has data", .. }
+        ));
+
+        fs::write(&module, "fn main() {}").unwrap();
+        let plan = |overwrite| plan_file(module.clone(), "module file", "new".into(), overwrite);
+        assert!(matches!(plan(false), Action::Keep { .. }));
+        assert_eq!(
+            plan(true),
+            Action::Overwrite {
+                path: module.clone(),
+                kind: "module file",
+                contents: "new".into(),
+            }
+        );
+
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn registers_modules_in_rustfmt_layout() {
+        let contents = "//! Days.\n\npub mod day02;\n\ncrate::solutions!(day02,);\n";
+        assert_eq!(
+            with_module(contents, "day01"),
+            "//! Days.\n\npub mod day01;\npub mod day02;\n\ncrate::solutions!(day01, day02,);\n"
+        );
+
+        let mut contents = "pub mod day01;\n\ncrate::solutions!(\n    day01,\n);\n".to_string();
+        for day in 2..=25 {
+            contents = with_module(&contents, &format!("day{day:02}"));
+        }
+        let registry = contents.split_once("\n\n").unwrap().1;
+        assert_eq!(
+            registry,
+            "crate::solutions!(\n  day01, day02, day03, day04, day05, day06, day07, day08, day09, day10, day11, day12, day13, day14,\n  day15, day16, day17, day18, day19, day20, day21, day22, day23, day24, day25,\n);\n"
+        );
+    }
+}
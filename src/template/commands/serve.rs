@@ -0,0 +1,298 @@
+use std::{
+    collections::HashMap,
+    io::{self, BufRead, BufReader, Write},
+    net::{TcpListener, TcpStream},
+    panic::{self, AssertUnwindSafe},
+    process, thread,
+    time::Duration,
+};
+
+use tinyjson::JsonValue;
+
+use crate::{
+    days,
+    template::{config, input, runner::Solved, timings::StoredTimings, Day},
+};
+
+pub const DEFAULT_PORT: u16 = 3000;
+
+/// Inputs larger than this are rejected.
+const MAX_BODY_SIZE: usize = 16 * 1024 * 1024;
+
+#[derive(Debug, PartialEq, Eq)]
+struct Request {
+    method: String,
+    path: String,
+    body: Vec<u8>,
+}
+
+#[derive(Debug, PartialEq)]
+struct Response {
+    status: u16,
+    body: JsonValue,
+}
+
+impl Response {
+    fn ok(body: JsonValue) -> Self {
+        Response { status: 200, body }
+    }
+
+    fn error(status: u16, message: impl Into<String>) -> Self {
+        let mut map = HashMap::new();
+        map.insert("error".into(), JsonValue::String(message.into()));
+        Response {
+            status,
+            body: JsonValue::Object(map),
+        }
+    }
+}
+
+/// Serves the solutions over HTTP on localhost until the process is stopped.
+pub fn handle(port: u16) {
+    let listener = TcpListener::bind(("127.0.0.1", port)).unwrap_or_else(|e| {
+        eprintln!("Failed to listen on port {port}: {e}");
+        process::exit(1);
+    });
+
+    println!(
+        "🎄 Serving {} day(s) on http://127.0.0.1:{port}",
+        days::DAYS.len()
+    );
+    println!("   POST /solve/<day>/<part> with the input as body, GET /status, GET /timings");
+
+    for stream in listener.incoming() {
+        match stream {
+            Ok(stream) => {
+                thread::spawn(move || {
+                    if let Err(e) = handle_connection(stream) {
+                        eprintln!("Failed to handle request: {e}");
+                    }
+                });
+            }
+            Err(e) => eprintln!("Failed to accept connection: {e}"),
+        }
+    }
+}
+
+fn handle_connection(mut stream: TcpStream) -> Result<(), io::Error> {
+    stream.set_read_timeout(Some(Duration::from_secs(30)))?;
+
+    let response = match read_request(&mut BufReader::new(&stream)) {
+        Ok(request) => {
+            println!("{} {}", request.method, request.path);
+            route(&request)
+        }
+        Err(e) => Response::error(400, e),
+    };
+
+    write_response(&mut stream, &response)
+}
+
+/// Reads an HTTP/1.1 request. Only `Content-Length` bodies are supported.
+fn read_request(reader: &mut impl BufRead) -> Result<Request, String> {
+    let mut line = String::new();
+    reader.read_line(&mut line).map_err(|e| e.to_string())?;
+
+    let mut parts = line.split_whitespace();
+    let (Some(method), Some(path)) = (parts.next(), parts.next()) else {
+        return Err("malformed request line.".into());
+    };
+    let (method, path) = (method.to_string(), path.to_string());
+
+    let mut content_length = 0;
+    loop {
+        line.clear();
+        if reader.read_line(&mut line).map_err(|e| e.to_string())? == 0 {
+            return Err("unexpected end of headers.".into());
+        }
+
+        let header = line.trim_end();
+        if header.is_empty() {
+            break;
+        }
+
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value
+                    .trim()
+                    .parse()
+                    .map_err(|_| "invalid content-length header.")?;
+            }
+        }
+    }
+
+    if content_length > MAX_BODY_SIZE {
+        return Err(format!("body exceeds {MAX_BODY_SIZE} bytes."));
+    }
+
+    let mut body = vec![0; content_length];
+    reader.read_exact(&mut body).map_err(|e| e.to_string())?;
+
+    Ok(Request { method, path, body })
+}
+
+fn write_response(stream: &mut impl Write, response: &Response) -> Result<(), io::Error> {
+    let body = response
+        .body
+        .stringify()
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+    let reason = match response.status {
+        200 => "OK",
+        400 => "Bad Request",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        _ => "Internal Server Error",
+    };
+
+    write!(
+        stream,
+        "HTTP/1.1 {} {reason}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+        response.status,
+        body.len()
+    )?;
+    stream.flush()
+}
+
+fn route(request: &Request) -> Response {
+    let path = request.path.split('?').next().unwrap_or_default();
+    let segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+
+    match (request.method.as_str(), segments.as_slice()) {
+        ("GET", ["status"]) => status(),
+        ("GET", ["timings"]) => timings(),
+        ("POST", ["solve", day, part]) => solve(day, part, &request.body),
+        (_, ["status" | "timings"] | ["solve", _, _]) => {
+            Response::error(405, "method not allowed.")
+        }
+        _ => Response::error(404, "not found."),
+    }
+}
+
+fn status() -> Response {
+    let mut map = HashMap::new();
+    map.insert("status".into(), JsonValue::String("ok".into()));
+    if let Some(year) = config::get().year() {
+        map.insert("year".into(), JsonValue::Number(year.into()));
+    }
+    map.insert(
+        "days".into(),
+        JsonValue::Array(
+            days::DAYS
+                .iter()
+                .map(|day| JsonValue::Number(day.into_inner().into()))
+                .collect(),
+        ),
+    );
+    Response::ok(JsonValue::Object(map))
+}
+
+fn timings() -> Response {
+    Response::ok(JsonValue::from(StoredTimings::read_from_file()))
+}
+
+fn solve(day: &str, part: &str, body: &[u8]) -> Response {
+    let Ok(day) = day.parse::<Day>() else {
+        return Response::error(404, format!("invalid day \"{day}\"."));
+    };
+    let part = match part.parse::<u8>() {
+        Ok(part @ (1 | 2)) => part,
+        _ => return Response::error(404, format!("invalid part \"{part}\", expected 1 or 2.")),
+    };
+    if !days::DAYS.contains(&day) {
+        return Response::error(404, format!("day {day} has not been solved yet."));
+    }
+
+    let Ok(body) = std::str::from_utf8(body) else {
+        return Response::error(400, "input is not valid UTF-8.");
+    };
+    let input = input::normalize(body);
+    if input::validate(&input).is_err() {
+        return Response::error(400, "input is empty.");
+    }
+
+    // a panicking solution should not take the server down.
+    match panic::catch_unwind(AssertUnwindSafe(|| days::solve(day, part, &input))) {
+        Ok(Some(solved)) => Response::ok(solved_json(day, part, &solved)),
+        Ok(None) => Response::error(404, format!("day {day} has not been solved yet.")),
+        Err(_) => Response::error(500, "the solution panicked, see the server log."),
+    }
+}
+
+fn solved_json(day: Day, part: u8, solved: &Solved) -> JsonValue {
+    let mut map = HashMap::new();
+    map.insert("day".into(), JsonValue::Number(day.into_inner().into()));
+    map.insert("part".into(), JsonValue::Number(part.into()));
+
+    // answers drawn as block letters are returned as text.
+    map.insert(
+        "answer".into(),
+        solved.answer().map_or(JsonValue::Null, JsonValue::String),
+    );
+
+    map.insert(
+        "time".into(),
+        JsonValue::String(format!("{:.1?}", solved.duration)),
+    );
+    map.insert(
+        "nanos".into(),
+        JsonValue::Number(solved.duration.as_nanos() as f64),
+    );
+    if let Some(parse) = solved.parse {
+        map.insert(
+            "parse_time".into(),
+            JsonValue::String(format!("{parse:.1?}")),
+        );
+        map.insert(
+            "parse_nanos".into(),
+            JsonValue::Number(parse.as_nanos() as f64),
+        );
+    }
+
+    JsonValue::Object(map)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{read_request, route, Request};
+
+    fn request(method: &str, path: &str) -> Request {
+        Request {
+            method: method.into(),
+            path: path.into(),
+            body: vec![],
+        }
+    }
+
+    #[test]
+    fn reads_requests() {
+        let raw = "POST /solve/1/2 HTTP/1.1\r\nHost: localhost\r\nContent-Length: 5\r\n\r\n1 2\n3";
+        let request = read_request(&mut raw.as_bytes()).unwrap();
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/solve/1/2");
+        assert_eq!(request.body, b"1 2\n3");
+
+        let raw = "GET /status HTTP/1.1\r\n\r\n";
+        assert_eq!(read_request(&mut raw.as_bytes()).unwrap().body, b"");
+
+        assert!(read_request(&mut "\r\n".as_bytes()).is_err());
+        assert!(read_request(&mut "GET /status HTTP/1.1\r\n".as_bytes()).is_err());
+        assert!(read_request(
+            &mut "POST / HTTP/1.1\r\nContent-Length: 999999999\r\n\r\n".as_bytes()
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn routes_requests() {
+        assert_eq!(route(&request("GET", "/status")).status, 200);
+        assert_eq!(route(&request("GET", "/status?verbose")).status, 200);
+        assert_eq!(route(&request("POST", "/status")).status, 405);
+        assert_eq!(route(&request("GET", "/solve/1/1")).status, 405);
+        assert_eq!(route(&request("GET", "/unknown")).status, 404);
+        assert_eq!(route(&request("POST", "/solve/26/1")).status, 404);
+        assert_eq!(route(&request("POST", "/solve/1/3")).status, 404);
+        assert_eq!(route(&request("POST", "/solve/1/1")).status, 400);
+    }
+}
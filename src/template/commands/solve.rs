@@ -1,23 +1,73 @@
-use std::process::{Command, Stdio};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    process::{self, Command, Stdio},
+};
 
-use crate::template::Day;
+#[cfg(feature = "solutions")]
+use crate::days;
+use crate::template::{
+    config,
+    differential::{self, Outcome},
+    input, manifest,
+    runner::Solved,
+    terminal, Day,
+};
+
+/// Extension of the files with the expected answers for an input, see [`handle_inputs`].
+const ANSWERS_EXTENSION: &str = "answers";
+
+pub fn handle(
+    day: Day,
+    release: bool,
+    dhat: bool,
+    alloc: bool,
+    visualize: bool,
+    trace: bool,
+    submit_part: Option<u8>,
+) {
+    manifest::warn_if_modified(day);
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
     let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+    let mut features = vec![];
 
     if dhat {
-        cmd_args.extend([
-            "--profile".to_string(),
-            "dhat".to_string(),
-            "--features".to_string(),
-            "dhat-heap".to_string(),
-        ]);
-    } else if release {
+        cmd_args.extend(["--profile".to_string(), "dhat".to_string()]);
+        features.push("dhat-heap");
+    } else if release || config::get().release {
         cmd_args.push("--release".to_string());
     }
 
+    if alloc {
+        if dhat {
+            eprintln!("Warning: `--alloc` has no effect together with `--dhat`.");
+        } else {
+            features.push("alloc-stats");
+        }
+    }
+
+    if visualize {
+        features.push("visualize");
+    }
+
+    if trace {
+        features.push("trace");
+    }
+
+    if !features.is_empty() {
+        cmd_args.extend(["--features".to_string(), features.join(",")]);
+    }
+
     cmd_args.push("--".to_string());
 
+    if visualize {
+        cmd_args.push("--visualize".to_string());
+    }
+
+    if trace {
+        cmd_args.push("--trace".to_string());
+    }
+
     if let Some(submit_part) = submit_part {
         cmd_args.push("--submit".to_string());
         cmd_args.push(submit_part.to_string());
@@ -30,5 +80,218 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         .spawn()
         .unwrap();
 
-    cmd.wait().unwrap();
+    let status = cmd.wait().unwrap();
+    if !status.success() {
+        process::exit(status.code().unwrap_or(1));
+    }
+}
+
+/// Runs both parts of a day in-process against every input in `dir`, e.g. the inputs of a team.
+/// Answers are compared against `<name>.answers` next to an input, with the answer of part 1 on the first line.
+#[cfg(feature = "solutions")]
+pub fn handle_inputs(day: Day, dir: &Path) {
+    if !days::DAYS.contains(&day) {
+        eprintln!("Day {day} is not registered in \"src/days/mod.rs\".");
+        process::exit(1);
+    }
+
+    if run_inputs(day, dir, |part, input| days::solve(day, part, input)) {
+        process::exit(1);
+    }
+}
+
+/// The solutions are only linked into the CLI with the `solutions` feature, so the CLI runs itself again with it.
+#[cfg(not(feature = "solutions"))]
+pub fn handle_inputs(day: Day, dir: &Path) {
+    let status = Command::new("cargo")
+        .args(["run", "--quiet", "--release", "--features", "solutions", "--"])
+        .args(["solve", &day.to_string(), "--inputs"])
+        .arg(dir)
+        .status();
+
+    match status {
+        Ok(status) if status.success() => {}
+        Ok(status) => process::exit(status.code().unwrap_or(1)),
+        Err(e) => {
+            eprintln!("Failed to run cargo: {e}");
+            process::exit(1);
+        }
+    }
+}
+
+/// Solves both parts of every input in `dir` with `solve` and prints a table of the results.
+/// Returns whether a part failed, i.e. panicked or gave a wrong answer.
+pub fn run_inputs(day: Day, dir: &Path, solve: impl Fn(u8, &str) -> Option<Solved>) -> bool {
+    let files = match list_inputs(dir) {
+        Ok(files) if !files.is_empty() => files,
+        Ok(_) => {
+            eprintln!("No inputs found in \"{}\".", dir.display());
+            process::exit(1);
+        }
+        Err(e) => {
+            eprintln!("Failed to read \"{}\": {e}", dir.display());
+            process::exit(1);
+        }
+    };
+
+    let mut rows = vec![vec![
+        "Input".to_string(),
+        "Part 1".to_string(),
+        "Part 2".to_string(),
+    ]];
+    let mut failed = false;
+
+    for path in &files {
+        let name = path
+            .file_name()
+            .map(|name| name.to_string_lossy().to_string())
+            .unwrap_or_default();
+
+        let input = match input::load_path(path, "", day) {
+            Ok(input) => input,
+            Err(e) => {
+                failed = true;
+                rows.push(vec![name, format!("✖ {e}"), String::new()]);
+                continue;
+            }
+        };
+        let expected = read_answers(path);
+
+        let mut row = vec![name];
+        for part in [1, 2] {
+            let (cell, is_failure) = solve_cell(
+                |input| solve(part, input),
+                &input,
+                expected.get(usize::from(part) - 1),
+            );
+            failed |= is_failure;
+            row.push(cell);
+        }
+        rows.push(row);
+    }
+
+    terminal::print_table(&rows);
+    failed
+}
+
+/// Lists the inputs in `dir`, sorted by name. Answer files and hidden files are skipped.
+fn list_inputs(dir: &Path) -> Result<Vec<PathBuf>, io::Error> {
+    let mut files = vec![];
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        let is_hidden = path
+            .file_name()
+            .is_some_and(|name| name.to_string_lossy().starts_with('.'));
+        let is_answers = path.extension().is_some_and(|ext| ext == ANSWERS_EXTENSION);
+        if path.is_file() && !is_hidden && !is_answers {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Reads the expected answers of an input. Empty lines or `?` leave a part unchecked.
+fn read_answers(input: &Path) -> Vec<Option<String>> {
+    fs::read_to_string(input.with_extension(ANSWERS_EXTENSION))
+        .map(|answers| {
+            answers
+                .lines()
+                .map(str::trim)
+                .map(|line| (!line.is_empty() && line != "?").then(|| line.to_string()))
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// Formats the result of a part, and whether it failed, i.e. panicked or gave a wrong answer.
+fn solve_cell(
+    solve: impl Fn(&str) -> Option<Solved>,
+    input: &str,
+    expected: Option<&Option<String>>,
+) -> (String, bool) {
+    let outcome = differential::silence_panics(|| differential::run(solve, input));
+
+    let solved = match outcome {
+        Outcome::Returned(Some(solved)) => solved,
+        Outcome::Returned(None) => return ("✖ not registered".into(), true),
+        Outcome::Panicked(message) => return (format!("✖ panicked: {message}"), true),
+    };
+
+    let time = format!("({:.1?})", solved.duration);
+    match (solved.answer(), expected) {
+        (None, _) => (format!("✖ {time}"), false),
+        (Some(answer), Some(Some(expected))) if answer != *expected => (
+            format!("{answer} ✖ expected {expected} {time}"),
+            true,
+        ),
+        (Some(answer), Some(Some(_))) => (format!("{answer} ✔ {time}"), false),
+        (Some(answer), _) => (format!("{answer} {time}"), false),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{fs, time::Duration};
+
+    use super::{list_inputs, read_answers, solve_cell};
+    use crate::template::runner::Solved;
+
+    fn solved(answer: &str) -> Option<Solved> {
+        Some(Solved {
+            result: Some(answer.into()),
+            parse: None,
+            duration: Duration::from_micros(3),
+        })
+    }
+
+    #[test]
+    fn lists_and_reads_inputs() {
+        let dir = std::env::temp_dir().join(format!("aoc-solve-inputs-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("bob.txt"), "2").unwrap();
+        fs::write(dir.join("alice.txt"), "1").unwrap();
+        fs::write(dir.join("alice.answers"), "?\n\n  42  \n").unwrap();
+        fs::write(dir.join(".hidden"), "3").unwrap();
+
+        let files = list_inputs(&dir).unwrap();
+        let answers = read_answers(&dir.join("alice.txt"));
+        let missing = read_answers(&dir.join("bob.txt"));
+        fs::remove_dir_all(&dir).unwrap();
+
+        assert_eq!(files, [dir.join("alice.txt"), dir.join("bob.txt")]);
+        assert_eq!(answers, [None, None, Some("42".to_string())]);
+        assert!(missing.is_empty());
+    }
+
+    #[test]
+    fn classifies_cells() {
+        let expected = Some("42".to_string());
+
+        assert_eq!(
+            solve_cell(|_| solved("42"), "", Some(&expected)),
+            ("42 ✔ (3.0µs)".to_string(), false)
+        );
+        assert_eq!(
+            solve_cell(|_| solved("41"), "", Some(&expected)),
+            ("41 ✖ expected 42 (3.0µs)".to_string(), true)
+        );
+        assert_eq!(
+            solve_cell(|_| solved("41"), "", Some(&None)),
+            ("41 (3.0µs)".to_string(), false)
+        );
+        assert_eq!(
+            solve_cell(|_| panic!("attempt to subtract with overflow"), "", None),
+            (
+                "✖ panicked: attempt to subtract with overflow".to_string(),
+                true
+            )
+        );
+        assert_eq!(
+            solve_cell(|_| None, "", None),
+            ("✖ not registered".to_string(), true)
+        );
+    }
 }
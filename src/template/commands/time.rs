@@ -1,11 +1,42 @@
-use std::collections::HashSet;
+use std::{collections::HashSet, process, time::Duration};
 
+use crate::template::build::BuildProfile;
+use crate::template::machine::Machine;
 use crate::template::run_multi::run_multi;
-use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::timings::{StoredTimings, Timings};
+use crate::template::{all_days, readme_benchmarks, terminal, Day, ANSI_BOLD, ANSI_RESET};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
+/// Days are flagged as slower with more threads if they take 5% longer, so noise is not flagged.
+const SLOWER_TOLERANCE: f64 = 1.05;
+
+/// Feature that counts the allocations of every part, see `--alloc`.
+const ALLOC_FEATURE: &str = "alloc-stats";
+
+/// What `cargo time` compares, if anything.
+pub enum Comparison {
+    None,
+    /// Build profiles, see `--profiles`.
+    Profiles(Vec<String>),
+    /// Sizes of rayon's global thread pool, see `--threads`.
+    Threads(Vec<usize>),
+}
+
+pub fn handle(day: Option<Day>, run_all: bool, store: bool, alloc: bool, comparison: Comparison) {
+    match comparison {
+        Comparison::None => {}
+        Comparison::Profiles(profiles) => return handle_matrix(day, store, alloc, &profiles),
+        Comparison::Threads(threads) => return handle_threads(day, alloc, &threads),
+    }
+
+    let mut profile = BuildProfile::release();
+    if alloc {
+        profile = profile.with_feature(ALLOC_FEATURE);
+    }
+
+    // counting allocations slows solutions down, so these timings are stored separately.
+    let machine = Machine::detect(&profile.name, &profile.features);
+    let mut stored = StoredTimings::read_from_file();
+    let stored_timings = stored.get(&machine);
 
     let days_to_run = day.map_or_else(
         || {
@@ -21,14 +52,17 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
         |day| HashSet::from([day]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let (report, timings) = run_multi(&days_to_run, &profile, true);
+    let timings = timings.unwrap();
 
     if store {
         let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+        stored.insert(&machine, merged_timings.clone());
+        stored.store_file().unwrap();
 
         println!();
-        match readme_benchmarks::update(merged_timings) {
+        println!("Stored timings for machine profile \"{}\".", machine.key());
+        match readme_benchmarks::update(merged_timings, &machine) {
             Ok(()) => {
                 println!("Stored updated benchmarks.");
             }
@@ -37,4 +71,273 @@ pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
             }
         }
     }
+
+    if report.is_failure() {
+        process::exit(1);
+    }
+}
+
+/// Benchmarks the days with every profile, then prints their timings side by side.
+fn handle_matrix(day: Option<Day>, store: bool, alloc: bool, names: &[String]) {
+    let days_to_run = day.map_or_else(|| all_days().collect(), |day| HashSet::from([day]));
+    let profiles: Vec<BuildProfile> = names
+        .iter()
+        .map(|name| {
+            let profile = BuildProfile::parse(name);
+            if alloc {
+                profile.with_feature(ALLOC_FEATURE)
+            } else {
+                profile
+            }
+        })
+        .collect();
+
+    let mut stored = StoredTimings::read_from_file();
+    let mut results = vec![];
+    let mut failed = false;
+
+    for profile in &profiles {
+        println!("{ANSI_BOLD}Profile {}{ANSI_RESET}", profile.name);
+        println!("==========");
+        println!();
+
+        let (report, timings) = run_multi(&days_to_run, profile, true);
+        let timings = timings.unwrap_or_default();
+        failed |= report.is_failure();
+
+        if store {
+            // the profile name is part of the machine key, so each profile is stored separately.
+            let machine = Machine::detect(&profile.name, &profile.features);
+            let merged_timings = stored.get(&machine).merge(&timings);
+            stored.insert(&machine, merged_timings);
+        }
+
+        results.push(timings);
+        println!();
+    }
+
+    if store {
+        match stored.store_file() {
+            Ok(()) => println!("Stored timings for {} profile(s).\n", profiles.len()),
+            Err(e) => eprintln!("Failed to store timings: {e}\n"),
+        }
+    }
+
+    terminal::print_table(&matrix(&profiles, &results));
+
+    if failed {
+        process::exit(1);
+    }
+}
+
+/// Benchmarks the days with every thread count, then prints how well they scale.
+fn handle_threads(day: Option<Day>, alloc: bool, threads: &[usize]) {
+    let days_to_run = day.map_or_else(|| all_days().collect(), |day| HashSet::from([day]));
+
+    let mut results = vec![];
+    let mut failed = false;
+
+    for &count in threads {
+        println!("{ANSI_BOLD}Threads: {count}{ANSI_RESET}");
+        println!("==========");
+        println!();
+
+        let mut profile = BuildProfile::release().with_threads(count);
+        if alloc {
+            profile = profile.with_feature(ALLOC_FEATURE);
+        }
+
+        let (report, timings) = run_multi(&days_to_run, &profile, true);
+        failed |= report.is_failure();
+        results.push(timings.unwrap_or_default());
+        println!();
+    }
+
+    let (rows, slower) = scaling(threads, &results);
+    terminal::print_table(&rows);
+
+    for day in slower {
+        println!(
+            "⚠ Day {day} is slower with more threads than with {}. Parallel overhead, e.g. of `par_bridge`, outweighs the gain.",
+            threads[0]
+        );
+    }
+
+    if failed {
+        process::exit(1);
+    }
+}
+
+/// One row per day with the time, speedup and parallel efficiency of each thread count,
+/// relative to the first thread count. Also returns the days that are slower with any larger count.
+#[allow(clippy::cast_precision_loss)]
+fn scaling(threads: &[usize], results: &[Timings]) -> (Vec<Vec<String>>, Vec<Day>) {
+    let mut header = vec!["Day".to_string()];
+    header.extend(threads.iter().map(|count| match count {
+        1 => "1 thread".to_string(),
+        count => format!("{count} threads"),
+    }));
+
+    let mut rows = vec![header];
+    let mut slower = vec![];
+
+    for day in all_days() {
+        let times: Vec<Option<f64>> = results.iter().map(|timings| nanos(timings, day)).collect();
+        let Some(base) = times[0] else {
+            continue;
+        };
+
+        let mut row = vec![day.to_string()];
+        for (index, (time, count)) in times.iter().zip(threads).enumerate() {
+            let Some(time) = time else {
+                row.push("-".into());
+                continue;
+            };
+            if index == 0 {
+                row.push(format_nanos(*time));
+                continue;
+            }
+
+            let speedup = base / time;
+            let efficiency = speedup * threads[0] as f64 / *count as f64;
+            row.push(format!(
+                "{} {speedup:.2}× {:.0}%",
+                format_nanos(*time),
+                efficiency * 100.0
+            ));
+        }
+
+        let is_slower = times
+            .iter()
+            .zip(threads)
+            .skip(1)
+            .any(|(time, count)| {
+                *count > threads[0] && time.is_some_and(|time| time > base * SLOWER_TOLERANCE)
+            });
+        if is_slower {
+            slower.push(day);
+        }
+
+        rows.push(row);
+    }
+
+    (rows, slower)
+}
+
+fn nanos(timings: &Timings, day: Day) -> Option<f64> {
+    timings
+        .data
+        .iter()
+        .find(|t| t.day == day && t.total_nanos > 0.0)
+        .map(|t| t.total_nanos)
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn format_nanos(nanos: f64) -> String {
+    format!("{:.1?}", Duration::from_nanos(nanos as u64))
+}
+
+/// One row per day and a total, with the time of each profile relative to the first one.
+fn matrix(profiles: &[BuildProfile], results: &[Timings]) -> Vec<Vec<String>> {
+    let mut header = vec!["Day".to_string()];
+    header.extend(profiles.iter().map(|p| p.name.clone()));
+
+    let mut rows = vec![header];
+    for day in all_days() {
+        let times: Vec<Option<f64>> = results.iter().map(|timings| nanos(timings, day)).collect();
+        if times.iter().all(Option::is_none) {
+            continue;
+        }
+        let mut row = vec![day.to_string()];
+        row.extend(times.iter().map(|time| format_cell(*time, times[0])));
+        rows.push(row);
+    }
+
+    let totals: Vec<Option<f64>> = results
+        .iter()
+        .map(|timings| Some(timings.total_millis() * 1_000_000_f64))
+        .collect();
+    let mut row = vec!["Total".to_string()];
+    row.extend(totals.iter().map(|total| format_cell(*total, totals[0])));
+    rows.push(row);
+
+    rows
+}
+
+fn format_cell(nanos: Option<f64>, baseline: Option<f64>) -> String {
+    let Some(nanos) = nanos else {
+        return "-".into();
+    };
+
+    let time = format_nanos(nanos);
+    match baseline {
+        Some(baseline) if baseline > 0.0 && baseline != nanos => {
+            format!("{time} ({:.2}×)", nanos / baseline)
+        }
+        _ => time,
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{matrix, scaling};
+    use crate::template::{
+        build::BuildProfile,
+        timings::{Timing, Timings},
+        Day,
+    };
+
+    fn timings(days: &[(u8, f64)]) -> Timings {
+        Timings {
+            data: days
+                .iter()
+                .map(|&(day, total_nanos)| Timing {
+                    day: Day::new(day).unwrap(),
+                    parse: None,
+                    part_1: None,
+                    part_2: None,
+                    part_1_allocs: None,
+                    part_2_allocs: None,
+                    part_1_usage: None,
+                    part_2_usage: None,
+                    total_nanos,
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn builds_comparison_matrix() {
+        let profiles = [BuildProfile::parse("release"), BuildProfile::parse("native")];
+        let results = [
+            timings(&[(1, 2_000_000.0), (6, 10_000.0)]),
+            timings(&[(1, 1_000_000.0)]),
+        ];
+
+        let rows = matrix(&profiles, &results);
+        assert_eq!(rows[0], vec!["Day", "release", "native"]);
+        assert_eq!(rows[1], vec!["01", "2.0ms", "1.0ms (0.50×)"]);
+        assert_eq!(rows[2], vec!["06", "10.0µs", "-"]);
+        assert_eq!(rows[3], vec!["Total", "2.0ms", "1.0ms (0.50×)"]);
+    }
+
+    #[test]
+    fn reports_thread_scaling() {
+        let results = [
+            timings(&[(6, 40_000_000.0), (10, 2_000_000.0)]),
+            timings(&[(6, 20_000_000.0), (10, 1_900_000.0)]),
+            timings(&[(6, 12_500_000.0), (10, 2_500_000.0)]),
+        ];
+
+        let (rows, slower) = scaling(&[1, 2, 4], &results);
+        assert_eq!(rows[0], vec!["Day", "1 thread", "2 threads", "4 threads"]);
+        assert_eq!(
+            rows[1],
+            vec!["06", "40.0ms", "20.0ms 2.00× 100%", "12.5ms 3.20× 80%"]
+        );
+        assert_eq!(rows[2][3], "2.5ms 0.80× 20%");
+        assert_eq!(slower, vec![Day::new(10).unwrap()]);
+    }
 }
@@ -0,0 +1,171 @@
+use std::{
+    io::{stdout, Write},
+    process, thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+
+use chrono::{DateTime, Datelike, Utc};
+
+use crate::template::{
+    aoc_cli,
+    commands::{download, read, scaffold},
+    config, Day,
+};
+
+/// Download attempts after the unlock, waiting twice as long after every failure.
+const DOWNLOAD_ATTEMPTS: u32 = 8;
+const INITIAL_BACKOFF: Duration = Duration::from_secs(5);
+const MAX_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Waits a bit after the unlock, so not everyone hits the servers in the same second.
+const MIN_JITTER_MS: u64 = 1000;
+const MAX_JITTER_MS: u64 = 5000;
+
+/// Scaffolds, downloads and reads the current day. With `wait`, waits for the next day to unlock first.
+pub fn handle(wait: bool) {
+    if wait {
+        handle_wait();
+        return;
+    }
+
+    let Some(day) = Day::today() else {
+        eprintln!(
+            "`today` command can only be run between the 1st and \
+            the 25th of december. Please use `scaffold` with a specific day, or `today --wait`."
+        );
+        process::exit(1)
+    };
+
+    scaffold::handle(day, false, false);
+    download::handle(day);
+    read::handle(day);
+}
+
+fn handle_wait() {
+    // fail before waiting, not after.
+    if aoc_cli::check().is_err() {
+        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
+        process::exit(1);
+    }
+
+    let Some((day, unlock)) = Day::next_unlock() else {
+        eprintln!("Failed to compute when the next puzzle unlocks.");
+        process::exit(1)
+    };
+
+    // aoc-cli downloads the configured year, so waiting would fetch the day of another year.
+    if let Err(year) = check_year(config::get().year(), unlock.year()) {
+        eprintln!(
+            "The year is set to {year} in aoc.toml or `AOC_YEAR`, but day {day} unlocks in {}. \
+            Set the year to {} to wait for it.",
+            unlock.year(),
+            unlock.year()
+        );
+        process::exit(1)
+    }
+
+    println!("⏳ Waiting for day {day} to unlock at {unlock}.");
+    countdown(day, unlock);
+    thread::sleep(jitter());
+
+    scaffold::handle(day, false, false);
+    download_with_backoff(day);
+    read::handle(day);
+}
+
+/// Fails with the configured year if it is set and not the year of the unlock.
+fn check_year(configured: Option<u16>, unlock_year: i32) -> Result<(), u16> {
+    match configured {
+        Some(year) if i32::from(year) != unlock_year => Err(year),
+        _ => Ok(()),
+    }
+}
+
+fn countdown(day: Day, unlock: DateTime<Utc>) {
+    let mut stdout = stdout();
+
+    // a negative duration does not convert, i.e. the day unlocked.
+    while let Ok(remaining) = (unlock - Utc::now()).to_std() {
+        if remaining.is_zero() {
+            break;
+        }
+
+        let secs = remaining.as_secs() + u64::from(remaining.subsec_nanos() > 0);
+        print!("\r⏳ Day {day} unlocks in {}   ", format_remaining(secs));
+        let _ = stdout.flush();
+
+        // wake up on full seconds, so the countdown ticks evenly.
+        let tick = match remaining.subsec_nanos() {
+            0 => Duration::from_secs(1),
+            nanos => Duration::from_nanos(nanos.into()),
+        };
+        thread::sleep(tick.min(remaining));
+    }
+
+    println!("\r🔓 Day {day} unlocked.                          ");
+}
+
+fn format_remaining(secs: u64) -> String {
+    let (days, hours, minutes, secs) = (
+        secs / 86_400,
+        secs / 3600 % 24,
+        secs / 60 % 60,
+        secs % 60,
+    );
+    if days > 0 {
+        format!("{days}d {hours:02}:{minutes:02}:{secs:02}")
+    } else {
+        format!("{hours:02}:{minutes:02}:{secs:02}")
+    }
+}
+
+fn jitter() -> Duration {
+    let nanos = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map_or(0, |d| u64::from(d.subsec_nanos()));
+    Duration::from_millis(MIN_JITTER_MS + nanos % (MAX_JITTER_MS - MIN_JITTER_MS))
+}
+
+/// The input may not be available right at the unlock, so failed downloads are retried.
+fn download_with_backoff(day: Day) {
+    let mut backoff = INITIAL_BACKOFF;
+
+    for attempt in 1..=DOWNLOAD_ATTEMPTS {
+        match download::fetch(day) {
+            Ok(()) => return,
+            Err(e) if attempt < DOWNLOAD_ATTEMPTS => {
+                eprintln!(
+                    "Download of day {day} failed ({e}), retrying in {}s.",
+                    backoff.as_secs()
+                );
+                thread::sleep(backoff);
+                backoff = (backoff * 2).min(MAX_BACKOFF);
+            }
+            Err(e) => {
+                eprintln!("Failed to download day {day} after {DOWNLOAD_ATTEMPTS} attempts: {e}");
+                process::exit(1);
+            }
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{check_year, format_remaining};
+
+    #[test]
+    fn refuses_to_wait_for_another_year() {
+        assert_eq!(check_year(Some(2024), 2025), Err(2024));
+        assert_eq!(check_year(Some(2025), 2025), Ok(()));
+        assert_eq!(check_year(None, 2025), Ok(()));
+    }
+
+    #[test]
+    fn formats_remaining_time() {
+        assert_eq!(format_remaining(0), "00:00:00");
+        assert_eq!(format_remaining(3 * 3600 + 4 * 60 + 5), "03:04:05");
+        assert_eq!(format_remaining(2 * 86_400 + 59), "2d 00:00:59");
+    }
+}
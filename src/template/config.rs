@@ -0,0 +1,655 @@
+/// Project configuration, read from `aoc.toml` in the repository root.
+///
+/// The file supports the subset of TOML the template needs: `[table]` headers, `key = value` pairs with
+/// strings, integers, floats, booleans and flat arrays, as well as `#` comments.
+/// Every setting is optional and falls back to the template defaults.
+use std::{
+    collections::{HashMap, HashSet},
+    fmt::Display,
+    fs, io,
+    path::PathBuf,
+    process,
+    str::FromStr,
+    sync::OnceLock,
+};
+
+use crate::template::Day;
+
+pub const CONFIG_FILE_PATH: &str = "aoc.toml";
+
+const BENCHMARK_KEYS: [&str; 3] = ["budget_ms", "min_samples", "max_samples"];
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+/// Returns the project configuration, reading it on first access.
+/// Exits the process if `aoc.toml` exists but is malformed.
+pub fn get() -> &'static Config {
+    CONFIG.get_or_init(|| match Config::read_from_file() {
+        Ok(config) => config,
+        Err(e) => {
+            eprintln!("Error: failed to read \"{CONFIG_FILE_PATH}\": {e}");
+            process::exit(1);
+        }
+    })
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    String(String),
+    Integer(i64),
+    Float(f64),
+    Boolean(bool),
+    Array(Vec<Value>),
+}
+
+#[derive(Debug)]
+pub enum Error {
+    Parser(usize, String),
+    InvalidValue(String, String),
+    /// A key the template does not know, e.g. a typo.
+    UnknownKey(String),
+    IO(io::Error),
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Parser(line, msg) => write!(f, "line {line}: {msg}"),
+            Error::InvalidValue(key, expected) => write!(f, "expected `{key}` to be {expected}."),
+            Error::UnknownKey(key) => write!(f, "unknown key `{key}`."),
+            Error::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::IO(e)
+    }
+}
+
+/// Benchmark budget for `cargo time`: every part is run for approx. `budget_ms` milliseconds,
+/// but at least `min_samples` and at most `max_samples` times.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Benchmark {
+    pub budget_ms: u64,
+    pub min_samples: u128,
+    pub max_samples: u128,
+}
+
+impl Default for Benchmark {
+    fn default() -> Self {
+        Self {
+            budget_ms: 1000,
+            min_samples: 10,
+            max_samples: 10000,
+        }
+    }
+}
+
+/// A column of the benchmark table in the readme, selected with `report.columns`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Column {
+    /// The day, linked to its solution.
+    Day,
+    /// The puzzle title, linked to the puzzle page. Hidden if no puzzle was downloaded.
+    Title,
+    /// Hidden if no solution has a shared `parse` function.
+    Parse,
+    Part1,
+    Part2,
+    /// Stars earned, i.e. answers in the downloaded puzzle description.
+    Stars,
+    /// Peak RSS of the parts, measured on Linux.
+    Memory,
+    /// Lines of code of the solution, without blank lines, comments and tests.
+    LinesOfCode,
+}
+
+impl FromStr for Column {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "day" => Ok(Column::Day),
+            "title" => Ok(Column::Title),
+            "parse" => Ok(Column::Parse),
+            "part_1" => Ok(Column::Part1),
+            "part_2" => Ok(Column::Part2),
+            "stars" => Ok(Column::Stars),
+            "memory" => Ok(Column::Memory),
+            "loc" => Ok(Column::LinesOfCode),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Columns of the benchmark table if `report.columns` is not set.
+pub const DEFAULT_COLUMNS: [Column; 5] = [
+    Column::Day,
+    Column::Title,
+    Column::Parse,
+    Column::Part1,
+    Column::Part2,
+];
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Config {
+    pub year: Option<u16>,
+    pub release: bool,
+    /// Solutions that run longer are stopped by `cargo all` and `cargo time`.
+    pub timeout_secs: Option<u64>,
+    pub inputs_dir: PathBuf,
+    pub examples_dir: PathBuf,
+    pub puzzles_dir: PathBuf,
+    pub manifest_path: PathBuf,
+    pub input_key_path: PathBuf,
+    /// Files that `cargo scaffold` overwrites are backed up here.
+    pub backups_dir: PathBuf,
+    pub readme_path: PathBuf,
+    pub timings_path: PathBuf,
+    /// Columns of the benchmark table in the readme, in order.
+    pub columns: Vec<Column>,
+    pub benchmark: Benchmark,
+    pub day_benchmarks: HashMap<Day, Benchmark>,
+}
+
+impl Default for Config {
+    fn default() -> Self {
+        Self {
+            year: None,
+            release: false,
+            timeout_secs: None,
+            inputs_dir: PathBuf::from("data/inputs"),
+            examples_dir: PathBuf::from("data/examples"),
+            puzzles_dir: PathBuf::from("data/puzzles"),
+            manifest_path: PathBuf::from("data/manifest.json"),
+            input_key_path: PathBuf::from(".aoc-input-key"),
+            backups_dir: PathBuf::from("data/backups"),
+            readme_path: PathBuf::from("README.md"),
+            timings_path: PathBuf::from("data/timings.json"),
+            columns: DEFAULT_COLUMNS.to_vec(),
+            benchmark: Benchmark::default(),
+            day_benchmarks: HashMap::new(),
+        }
+    }
+}
+
+impl Config {
+    /// Reads `aoc.toml`. If not present, returns the default configuration.
+    pub fn read_from_file() -> Result<Self, Error> {
+        match fs::read_to_string(CONFIG_FILE_PATH) {
+            Ok(s) => Config::try_from(s.as_str()),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Config::default()),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// The year of the puzzles. The config file takes precedence over the `AOC_YEAR` env variable.
+    pub fn year(&self) -> Option<u16> {
+        self.year
+            .or_else(|| std::env::var("AOC_YEAR").ok()?.parse().ok())
+    }
+
+    /// Benchmark budget for a day, taking per-day overrides into account.
+    pub fn benchmark_for(&self, day: Day) -> Benchmark {
+        self.day_benchmarks
+            .get(&day)
+            .copied()
+            .unwrap_or(self.benchmark)
+    }
+
+    /// Resolves a data folder name (`inputs`, `examples`, `puzzles`) to its configured directory.
+    /// Unknown folders resolve to `data/<folder>`.
+    pub fn data_dir(&self, folder: &str) -> PathBuf {
+        match folder {
+            "inputs" => self.inputs_dir.clone(),
+            "examples" => self.examples_dir.clone(),
+            "puzzles" => self.puzzles_dir.clone(),
+            folder => PathBuf::from("data").join(folder),
+        }
+    }
+
+    pub fn input_path(&self, day: Day) -> PathBuf {
+        self.inputs_dir.join(format!("{day}.txt"))
+    }
+
+    pub fn example_path(&self, day: Day) -> PathBuf {
+        self.examples_dir.join(format!("{day}.txt"))
+    }
+
+    pub fn puzzle_path(&self, day: Day) -> PathBuf {
+        self.puzzles_dir.join(format!("{day}.md"))
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl TryFrom<&str> for Config {
+    type Error = Error;
+
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let table = parse(value)?;
+        let mut config = Config::default();
+
+        for (key, value) in &table {
+            match key.as_str() {
+                "year" => config.year = Some(as_int(key, value, "a year")?),
+                "run.release" => config.release = as_bool(key, value)?,
+                "run.timeout_secs" => {
+                    config.timeout_secs = Some(as_int(key, value, "a positive integer")?);
+                }
+                "paths.inputs" => config.inputs_dir = as_string(key, value)?.into(),
+                "paths.examples" => config.examples_dir = as_string(key, value)?.into(),
+                "paths.puzzles" => config.puzzles_dir = as_string(key, value)?.into(),
+                "paths.manifest" => config.manifest_path = as_string(key, value)?.into(),
+                "paths.input_key" => config.input_key_path = as_string(key, value)?.into(),
+                "paths.backups" => config.backups_dir = as_string(key, value)?.into(),
+                "report.readme" => config.readme_path = as_string(key, value)?.into(),
+                "report.timings" => config.timings_path = as_string(key, value)?.into(),
+                "report.columns" => config.columns = as_columns(key, value)?,
+                // read with their defaults below.
+                key if is_benchmark_key(key) => {}
+                _ => return Err(Error::UnknownKey(key.clone())),
+            }
+        }
+
+        config.benchmark = read_benchmark(&table, "benchmark", Benchmark::default())?;
+
+        let day_keys: HashSet<&str> = table
+            .keys()
+            .filter_map(|key| key.strip_prefix("benchmark.day.")?.split('.').next())
+            .collect();
+
+        for day_key in day_keys {
+            let day = day_key.parse::<Day>().map_err(|_| {
+                Error::InvalidValue(
+                    format!("benchmark.day.{day_key}"),
+                    "a day between 1 and 25".into(),
+                )
+            })?;
+            let benchmark = read_benchmark(
+                &table,
+                &format!("benchmark.day.{day_key}"),
+                config.benchmark,
+            )?;
+            config.day_benchmarks.insert(day, benchmark);
+        }
+
+        Ok(config)
+    }
+}
+
+/// Returns `true` for the keys of [`Benchmark`], e.g. `benchmark.budget_ms` or `benchmark.day.06.max_samples`.
+fn is_benchmark_key(key: &str) -> bool {
+    let Some(rest) = key.strip_prefix("benchmark.") else {
+        return false;
+    };
+    let name = match rest.strip_prefix("day.") {
+        Some(day_key) => match day_key.split_once('.') {
+            Some((_, name)) => name,
+            None => return false,
+        },
+        None => rest,
+    };
+    BENCHMARK_KEYS.contains(&name)
+}
+
+fn read_benchmark(
+    table: &HashMap<String, Value>,
+    prefix: &str,
+    defaults: Benchmark,
+) -> Result<Benchmark, Error> {
+    let get = |name: &str| {
+        let key = format!("{prefix}.{name}");
+        table
+            .get(&key)
+            .map(|v| as_int::<u64>(&key, v, "a positive integer"))
+            .transpose()
+    };
+
+    let benchmark = Benchmark {
+        budget_ms: get("budget_ms")?.unwrap_or(defaults.budget_ms),
+        min_samples: get("min_samples")?.map_or(defaults.min_samples, u128::from),
+        max_samples: get("max_samples")?.map_or(defaults.max_samples, u128::from),
+    };
+
+    if benchmark.min_samples == 0 || benchmark.min_samples > benchmark.max_samples {
+        return Err(Error::InvalidValue(
+            format!("{prefix}.min_samples"),
+            "at least 1 and not larger than `max_samples`".into(),
+        ));
+    }
+
+    Ok(benchmark)
+}
+
+fn as_string(key: &str, value: &Value) -> Result<String, Error> {
+    match value {
+        Value::String(s) => Ok(s.clone()),
+        _ => Err(Error::InvalidValue(key.into(), "a string".into())),
+    }
+}
+
+fn as_columns(key: &str, value: &Value) -> Result<Vec<Column>, Error> {
+    let expected = || {
+        Error::InvalidValue(
+            key.into(),
+            "an array of `day`, `title`, `parse`, `part_1`, `part_2`, `stars`, `memory` or `loc`"
+                .into(),
+        )
+    };
+
+    match value {
+        Value::Array(values) => values
+            .iter()
+            .map(|value| match value {
+                Value::String(s) => s.parse().map_err(|()| expected()),
+                _ => Err(expected()),
+            })
+            .collect(),
+        _ => Err(expected()),
+    }
+}
+
+fn as_bool(key: &str, value: &Value) -> Result<bool, Error> {
+    match value {
+        Value::Boolean(b) => Ok(*b),
+        _ => Err(Error::InvalidValue(key.into(), "a boolean".into())),
+    }
+}
+
+fn as_int<T: TryFrom<i64>>(key: &str, value: &Value, expected: &str) -> Result<T, Error> {
+    match value {
+        Value::Integer(i) => {
+            T::try_from(*i).map_err(|_| Error::InvalidValue(key.into(), expected.into()))
+        }
+        _ => Err(Error::InvalidValue(key.into(), expected.into())),
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Parses a TOML document into a flat map of dotted keys, e.g. `benchmark.budget_ms`.
+pub fn parse(s: &str) -> Result<HashMap<String, Value>, Error> {
+    let mut table = HashMap::new();
+    let mut section = String::new();
+
+    for (idx, raw_line) in s.lines().enumerate() {
+        let line_number = idx + 1;
+        let line = strip_comment(raw_line).trim();
+
+        if line.is_empty() {
+            continue;
+        }
+
+        if let Some(header) = line.strip_prefix('[') {
+            let header = header
+                .strip_suffix(']')
+                .ok_or_else(|| Error::Parser(line_number, "unterminated table header.".into()))?;
+            section = parse_key(header, line_number)?;
+            continue;
+        }
+
+        let (key, value) = line
+            .split_once('=')
+            .ok_or_else(|| Error::Parser(line_number, "expected `key = value`.".into()))?;
+
+        let key = parse_key(key, line_number)?;
+        let key = if section.is_empty() {
+            key
+        } else {
+            format!("{section}.{key}")
+        };
+
+        let value = parse_value(value.trim(), line_number)?;
+
+        if table.insert(key.clone(), value).is_some() {
+            return Err(Error::Parser(
+                line_number,
+                format!("duplicate key `{key}`."),
+            ));
+        }
+    }
+
+    Ok(table)
+}
+
+fn strip_comment(line: &str) -> &str {
+    unquoted(line, '#').next().map_or(line, |idx| &line[..idx])
+}
+
+/// Byte indices of `target` outside of strings. Escaped quotes do not end a string.
+fn unquoted(s: &str, target: char) -> impl Iterator<Item = usize> + '_ {
+    let mut in_string = false;
+    let mut escaped = false;
+    s.char_indices().filter_map(move |(idx, c)| {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            c if c == target && !in_string => return Some(idx),
+            _ => {}
+        }
+        None
+    })
+}
+
+fn parse_key(key: &str, line_number: usize) -> Result<String, Error> {
+    let parts = key
+        .split('.')
+        .map(|part| {
+            let part = part.trim();
+            let part = part
+                .strip_prefix('"')
+                .and_then(|p| p.strip_suffix('"'))
+                .unwrap_or(part);
+            if part.is_empty()
+                || !part
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+            {
+                Err(Error::Parser(line_number, format!("invalid key `{key}`.")))
+            } else {
+                Ok(part)
+            }
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(parts.join("."))
+}
+
+fn parse_value(value: &str, line_number: usize) -> Result<Value, Error> {
+    let invalid = || Error::Parser(line_number, format!("invalid value `{value}`."));
+
+    if let Some(s) = value.strip_prefix('"') {
+        return s
+            .strip_suffix('"')
+            .map(|s| Value::String(s.replace("\\\"", "\"").replace("\\\\", "\\")))
+            .ok_or_else(invalid);
+    }
+
+    if let Some(items) = value.strip_prefix('[') {
+        let items = items.strip_suffix(']').ok_or_else(invalid)?.trim();
+        let items = items.strip_suffix(',').unwrap_or(items);
+        if items.is_empty() {
+            return Ok(Value::Array(vec![]));
+        }
+
+        let mut start = 0;
+        return unquoted(items, ',')
+            .chain([items.len()])
+            .map(|end| {
+                let item = &items[start..end];
+                start = end + 1;
+                parse_value(item.trim(), line_number)
+            })
+            .collect::<Result<_, _>>()
+            .map(Value::Array);
+    }
+
+    match value {
+        "true" => Ok(Value::Boolean(true)),
+        "false" => Ok(Value::Boolean(false)),
+        v => {
+            let v = v.replace('_', "");
+            v.parse::<i64>()
+                .map(Value::Integer)
+                .or_else(|_| v.parse::<f64>().map(Value::Float))
+                .map_err(|_| invalid())
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::path::PathBuf;
+
+    use super::{parse, Benchmark, Column, Config, Error, Value};
+    use crate::day;
+
+    #[test]
+    fn parses_values() {
+        let table = parse(
+            r#"
+            year = 2023 # trailing comment
+            [run]
+            release = true
+            [paths]
+            inputs = "inputs/#private"
+            [benchmark]
+            budget_ms = 1_500
+            ratio = 0.5
+            days = [6, 10,]
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(table.get("year"), Some(&Value::Integer(2023)));
+        assert_eq!(table.get("run.release"), Some(&Value::Boolean(true)));
+        assert_eq!(
+            table.get("paths.inputs"),
+            Some(&Value::String("inputs/#private".into()))
+        );
+        assert_eq!(
+            table.get("benchmark.budget_ms"),
+            Some(&Value::Integer(1500))
+        );
+        assert_eq!(table.get("benchmark.ratio"), Some(&Value::Float(0.5)));
+        assert_eq!(
+            table.get("benchmark.days"),
+            Some(&Value::Array(vec![Value::Integer(6), Value::Integer(10)]))
+        );
+    }
+
+    #[test]
+    fn parses_comments_and_commas_in_strings() {
+        let table = parse(
+            r##"
+            inputs = "a \"#quoted\" dir" # comment with "quotes"
+            columns = ["a,b", "c#d", "e\"]"] # comment
+            "##,
+        )
+        .unwrap();
+
+        assert_eq!(
+            table.get("inputs"),
+            Some(&Value::String("a \"#quoted\" dir".into()))
+        );
+        assert_eq!(
+            table.get("columns"),
+            Some(&Value::Array(vec![
+                Value::String("a,b".into()),
+                Value::String("c#d".into()),
+                Value::String("e\"]".into()),
+            ]))
+        );
+    }
+
+    #[test]
+    fn defaults_for_empty_file() {
+        assert_eq!(Config::try_from("").unwrap(), Config::default());
+    }
+
+    #[test]
+    fn reads_config() {
+        let config = Config::try_from(
+            r#"
+            year = 2022
+            [run]
+            timeout_secs = 60
+            [paths]
+            inputs = "private/inputs"
+            [report]
+            timings = "bench/timings.json"
+            columns = ["day", "stars", "part_1", "loc"]
+            [benchmark]
+            budget_ms = 500
+            [benchmark.day.06]
+            budget_ms = 5000
+            max_samples = 20
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.year(), Some(2022));
+        assert_eq!(config.timeout_secs, Some(60));
+        assert_eq!(
+            config.input_path(day!(3)),
+            PathBuf::from("private/inputs/03.txt")
+        );
+        assert_eq!(config.timings_path, PathBuf::from("bench/timings.json"));
+        assert_eq!(
+            config.columns,
+            vec![Column::Day, Column::Stars, Column::Part1, Column::LinesOfCode]
+        );
+        assert_eq!(config.benchmark_for(day!(1)).budget_ms, 500);
+        assert_eq!(
+            config.benchmark_for(day!(6)),
+            Benchmark {
+                budget_ms: 5000,
+                min_samples: 10,
+                max_samples: 20,
+            }
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn errors_for_invalid_day_override() {
+        Config::try_from("[benchmark.day.26]\nbudget_ms = 1").unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn errors_for_wrong_type() {
+        Config::try_from("[run]\nrelease = \"yes\"").unwrap();
+    }
+
+    #[test]
+    #[should_panic]
+    fn errors_for_unknown_column() {
+        Config::try_from("[report]\ncolumns = [\"day\", \"speed\"]").unwrap();
+    }
+
+    #[test]
+    fn errors_for_unknown_keys() {
+        for (toml, unknown) in [
+            ("[run]\nrelese = true", "run.relese"),
+            ("[benchmark]\nbudget = 1", "benchmark.budget"),
+            ("[benchmark.day.06]\nsamples = 1", "benchmark.day.06.samples"),
+        ] {
+            assert!(
+                matches!(Config::try_from(toml), Err(Error::UnknownKey(key)) if key == unknown),
+                "{toml}"
+            );
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn errors_for_duplicate_keys() {
+        parse("year = 2022\nyear = 2023").unwrap();
+    }
+}
@@ -0,0 +1,319 @@
+/// Encryption at rest for puzzle inputs, so they can be committed without publishing them.
+///
+/// Files are encrypted with ChaCha20 (RFC 8439) and authenticated with HMAC-SHA-256 (encrypt-then-MAC),
+/// using the `chacha20`, `hmac` and `sha2` crates. Keys and nonces come from the OS via `getrandom`.
+/// The key is read from the `AOC_INPUT_KEY` env variable or the key file (`.aoc-input-key` by default).
+/// Encrypted files are text, so they diff and merge like any other file:
+///
+/// ```text
+/// aoc-encrypted-input v1
+/// nonce 5c0d4b0a...
+/// mac 8d1f02c3...
+/// <ciphertext as hex, 64 characters per line>
+/// ```
+use std::{fmt::Display, fs, io, path::Path};
+
+use chacha20::{
+    cipher::{KeyIvInit, StreamCipher, StreamCipherSeek},
+    ChaCha20,
+};
+
+use crate::template::{config, sha256};
+
+pub const KEY_ENV_VAR: &str = "AOC_INPUT_KEY";
+
+const HEADER: &str = "aoc-encrypted-input v1";
+const KEY_LEN: usize = 32;
+const NONCE_LEN: usize = 12;
+const LINE_WIDTH: usize = 64;
+
+#[derive(Debug)]
+pub enum CryptoError {
+    MissingKey,
+    InvalidKey(String),
+    Malformed(String),
+    /// The authentication tag does not match: wrong key or corrupted file.
+    WrongKey,
+    /// The OS could not provide random bytes for a key or nonce.
+    Random(getrandom::Error),
+    IO(io::Error),
+}
+
+impl Display for CryptoError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CryptoError::MissingKey => write!(
+                f,
+                "no input key found. Set `{KEY_ENV_VAR}` or add the key file \"{}\".",
+                config::get().input_key_path.display()
+            ),
+            CryptoError::InvalidKey(e) => write!(f, "invalid input key: {e}"),
+            CryptoError::Malformed(e) => write!(f, "malformed encrypted file: {e}"),
+            CryptoError::WrongKey => write!(
+                f,
+                "could not decrypt, the file was encrypted with a different key or is corrupted."
+            ),
+            CryptoError::Random(e) => write!(f, "could not get random bytes from the OS: {e}"),
+            CryptoError::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<io::Error> for CryptoError {
+    fn from(e: io::Error) -> Self {
+        CryptoError::IO(e)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A 256-bit input key. Encryption and authentication use separate keys derived from it.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Key([u8; KEY_LEN]);
+
+impl Key {
+    /// Generates a new random key.
+    pub fn generate() -> Result<Self, CryptoError> {
+        let mut key = [0; KEY_LEN];
+        random_bytes(&mut key)?;
+        Ok(Key(key))
+    }
+
+    pub fn from_hex(s: &str) -> Result<Self, CryptoError> {
+        let bytes = from_hex(s.trim()).map_err(CryptoError::InvalidKey)?;
+        let key: [u8; KEY_LEN] = bytes.try_into().map_err(|_| {
+            CryptoError::InvalidKey(format!("expected {} hex characters.", KEY_LEN * 2))
+        })?;
+        Ok(Key(key))
+    }
+
+    pub fn to_hex(&self) -> String {
+        sha256::hex(&self.0)
+    }
+
+    /// Reads the key from `AOC_INPUT_KEY` or the key file. `None` if neither is set.
+    pub fn load() -> Result<Option<Self>, CryptoError> {
+        if let Ok(key) = std::env::var(KEY_ENV_VAR) {
+            if !key.trim().is_empty() {
+                return Key::from_hex(&key).map(Some);
+            }
+        }
+
+        match fs::read_to_string(&config::get().input_key_path) {
+            Ok(key) => Key::from_hex(&key).map(Some),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e.into()),
+        }
+    }
+
+    /// Writes the key to the key file.
+    pub fn store_file(&self) -> Result<(), io::Error> {
+        fs::write(
+            &config::get().input_key_path,
+            format!("{}\n", self.to_hex()),
+        )
+    }
+
+    fn derive(&self, purpose: &[u8]) -> [u8; KEY_LEN] {
+        sha256::hmac(&self.0, purpose)
+    }
+}
+
+impl std::fmt::Debug for Key {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "Key(..)")
+    }
+}
+
+/// Returns `true` if `contents` is an encrypted file.
+pub fn is_encrypted(contents: &[u8]) -> bool {
+    contents.starts_with(HEADER.as_bytes())
+}
+
+/// Encrypts `plaintext` with a random nonce.
+pub fn encrypt(key: &Key, plaintext: &[u8]) -> Result<String, CryptoError> {
+    let mut nonce = [0; NONCE_LEN];
+    random_bytes(&mut nonce)?;
+    Ok(encrypt_with_nonce(key, plaintext, &nonce))
+}
+
+fn encrypt_with_nonce(key: &Key, plaintext: &[u8], nonce: &[u8; NONCE_LEN]) -> String {
+    let mut ciphertext = plaintext.to_vec();
+    apply_keystream(&key.derive(b"encryption"), nonce, &mut ciphertext);
+    let mac = sha256::hmac(
+        &key.derive(b"authentication"),
+        &authenticated_data(nonce, &ciphertext),
+    );
+
+    let mut out = format!(
+        "{HEADER}\nnonce {}\nmac {}\n",
+        sha256::hex(nonce),
+        sha256::hex(&mac)
+    );
+    for line in ciphertext.chunks(LINE_WIDTH / 2) {
+        out.push_str(&sha256::hex(line));
+        out.push('\n');
+    }
+    out
+}
+
+/// Verifies and decrypts an encrypted file.
+pub fn decrypt(key: &Key, contents: &[u8]) -> Result<Vec<u8>, CryptoError> {
+    let contents = std::str::from_utf8(contents)
+        .map_err(|_| CryptoError::Malformed("not valid UTF-8.".into()))?;
+    let mut lines = contents.lines();
+
+    if lines.next().map(str::trim_end) != Some(HEADER) {
+        return Err(CryptoError::Malformed(format!(
+            "expected \"{HEADER}\" header."
+        )));
+    }
+
+    let mut field = |name: &str| {
+        lines
+            .next()
+            .and_then(|l| l.trim_end().strip_prefix(name)?.strip_prefix(' '))
+            .ok_or_else(|| CryptoError::Malformed(format!("missing {name}.")))
+            .and_then(|v| from_hex(v).map_err(CryptoError::Malformed))
+    };
+
+    let nonce: [u8; NONCE_LEN] = field("nonce")?
+        .try_into()
+        .map_err(|_| CryptoError::Malformed("invalid nonce length.".into()))?;
+    let mac = field("mac")?;
+
+    let mut ciphertext = vec![];
+    for line in lines {
+        ciphertext.extend(from_hex(line.trim_end()).map_err(CryptoError::Malformed)?);
+    }
+
+    let data = authenticated_data(&nonce, &ciphertext);
+    if !sha256::verify_hmac(&key.derive(b"authentication"), &data, &mac) {
+        return Err(CryptoError::WrongKey);
+    }
+
+    apply_keystream(&key.derive(b"encryption"), &nonce, &mut ciphertext);
+    Ok(ciphertext)
+}
+
+/// Decrypts `contents` with the loaded key if it is encrypted, otherwise returns it unchanged.
+pub fn plain(contents: Vec<u8>) -> Result<Vec<u8>, CryptoError> {
+    if is_encrypted(&contents) {
+        let key = Key::load()?.ok_or(CryptoError::MissingKey)?;
+        decrypt(&key, &contents)
+    } else {
+        Ok(contents)
+    }
+}
+
+/// Reads a file, decrypting it if necessary.
+pub fn read_plain(path: &Path) -> Result<Vec<u8>, CryptoError> {
+    plain(fs::read(path)?)
+}
+
+/// The header, nonce and ciphertext, so none of them can be changed without failing authentication.
+fn authenticated_data(nonce: &[u8], ciphertext: &[u8]) -> Vec<u8> {
+    let mut data = HEADER.as_bytes().to_vec();
+    data.extend_from_slice(nonce);
+    data.extend_from_slice(ciphertext);
+    data
+}
+
+/// Encrypts or decrypts `data` in place, starting with block counter 1 like RFC 8439.
+fn apply_keystream(key: &[u8; KEY_LEN], nonce: &[u8; NONCE_LEN], data: &mut [u8]) {
+    let mut cipher = ChaCha20::new(key.into(), nonce.into());
+    cipher.seek(64);
+    cipher.apply_keystream(data);
+}
+
+fn from_hex(s: &str) -> Result<Vec<u8>, String> {
+    if !s.len().is_multiple_of(2) || !s.is_ascii() {
+        return Err("invalid hex string.".into());
+    }
+    (0..s.len())
+        .step_by(2)
+        .map(|i| u8::from_str_radix(&s[i..i + 2], 16).map_err(|e| e.to_string()))
+        .collect()
+}
+
+/// Fills `bytes` from the OS random number generator. There is no fallback if it fails.
+fn random_bytes(bytes: &mut [u8]) -> Result<(), CryptoError> {
+    getrandom::getrandom(bytes).map_err(CryptoError::Random)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{
+        apply_keystream, decrypt, encrypt, encrypt_with_nonce, is_encrypted, CryptoError, Key,
+        LINE_WIDTH,
+    };
+    use crate::template::sha256::hex;
+
+    fn test_key() -> Key {
+        Key::from_hex("000102030405060708090a0b0c0d0e0f101112131415161718191a1b1c1d1e1f").unwrap()
+    }
+
+    #[test]
+    fn matches_rfc_8439_test_vector() {
+        let key: [u8; 32] = core::array::from_fn(|i| i as u8);
+        let nonce = [0, 0, 0, 0, 0, 0, 0, 0x4a, 0, 0, 0, 0];
+        let mut data = b"Ladies and Gentlemen of the class of '99: If I could offer you only one tip for the future, sunscreen would be it.".to_vec();
+        apply_keystream(&key, &nonce, &mut data);
+        assert_eq!(
+            hex(&data[..32]),
+            "6e2e359a2568f98041ba0728dd0d6981e97e7aec1d4360c20a27afccfd9fae0b"
+        );
+        assert_eq!(hex(&data[data.len() - 2..]), "874d");
+    }
+
+    #[test]
+    fn roundtrips() {
+        let key = test_key();
+        let plaintext = "3   4\n4   3\n".repeat(100);
+        let encrypted = encrypt(&key, plaintext.as_bytes()).unwrap();
+
+        assert!(is_encrypted(encrypted.as_bytes()));
+        assert!(!encrypted.contains("3   4"));
+        assert!(encrypted.lines().skip(3).all(|l| l.len() <= LINE_WIDTH));
+        assert_eq!(
+            decrypt(&key, encrypted.as_bytes()).unwrap(),
+            plaintext.as_bytes()
+        );
+        assert_ne!(encrypt(&key, plaintext.as_bytes()).unwrap(), encrypted);
+    }
+
+    #[test]
+    fn rejects_wrong_keys_and_tampering() {
+        let key = test_key();
+        let encrypted = encrypt_with_nonce(&key, b"1 2 3", &[7; 12]);
+
+        assert!(matches!(
+            decrypt(&Key::generate().unwrap(), encrypted.as_bytes()),
+            Err(CryptoError::WrongKey)
+        ));
+
+        let last = encrypted.trim_end().len() - 1;
+        let mut tampered = encrypted.clone().into_bytes();
+        tampered[last] = if tampered[last] == b'0' { b'1' } else { b'0' };
+        assert!(matches!(
+            decrypt(&key, &tampered),
+            Err(CryptoError::WrongKey)
+        ));
+
+        assert!(matches!(
+            decrypt(&key, b"aoc-encrypted-input v1\nmac 00\n"),
+            Err(CryptoError::Malformed(_))
+        ));
+    }
+
+    #[test]
+    fn parses_keys() {
+        let key = test_key();
+        assert_eq!(Key::from_hex(&key.to_hex()).unwrap(), key);
+        assert!(Key::from_hex("abcd").is_err());
+        assert!(Key::from_hex("zz").is_err());
+        assert_ne!(Key::generate().unwrap(), Key::generate().unwrap());
+    }
+}
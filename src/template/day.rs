@@ -3,7 +3,7 @@ use std::fmt::Display;
 use std::str::FromStr;
 
 #[cfg(feature = "today")]
-use chrono::{Datelike, FixedOffset, Utc};
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, Utc};
 
 #[cfg(feature = "today")]
 const SERVER_UTC_OFFSET: i32 = -5;
@@ -38,7 +38,7 @@ impl Day {
     }
 
     /// Converts the [`Day`] into an [`u8`].
-    pub fn into_inner(self) -> u8 {
+    pub const fn into_inner(self) -> u8 {
         self.0
     }
 }
@@ -55,6 +55,27 @@ impl Day {
             None
         }
     }
+
+    /// Returns the next day to unlock and the instant its puzzle unlocks, i.e. midnight at the server.
+    pub fn next_unlock() -> Option<(Self, DateTime<Utc>)> {
+        Self::next_unlock_after(Utc::now())
+    }
+
+    fn next_unlock_after(now: DateTime<Utc>) -> Option<(Self, DateTime<Utc>)> {
+        let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
+        let today = now.with_timezone(&offset).date_naive();
+        let (year, day) = match (today.month(), today.day()) {
+            (12, day) if day < 25 => (today.year(), day + 1),
+            (12, _) => (today.year() + 1, 1),
+            _ => (today.year(), 1),
+        };
+
+        let unlock = NaiveDate::from_ymd_opt(year, 12, day)?
+            .and_hms_opt(0, 0, 0)?
+            .and_local_timezone(offset)
+            .single()?;
+        Some((Self::new(u8::try_from(day).ok()?)?, unlock.with_timezone(&Utc)))
+    }
 }
 
 impl Display for Day {
@@ -187,6 +208,32 @@ mod tests {
         assert_eq!(iter.next(), Some(Day(25)));
         assert_eq!(iter.next(), None);
     }
+
+    #[cfg(feature = "today")]
+    #[test]
+    fn next_unlock() {
+        use chrono::{DateTime, Utc};
+
+        let at = |s: &str| s.parse::<DateTime<Utc>>().unwrap();
+        let unlock = |now: &str| Day::next_unlock_after(at(now)).unwrap();
+
+        assert_eq!(
+            unlock("2024-12-05T04:59:59Z"),
+            (Day(5), at("2024-12-05T05:00:00Z"))
+        );
+        assert_eq!(
+            unlock("2024-12-05T05:00:00Z"),
+            (Day(6), at("2024-12-06T05:00:00Z"))
+        );
+        assert_eq!(
+            unlock("2024-07-01T12:00:00Z"),
+            (Day(1), at("2024-12-01T05:00:00Z"))
+        );
+        assert_eq!(
+            unlock("2024-12-25T06:00:00Z"),
+            (Day(1), at("2025-12-01T05:00:00Z"))
+        );
+    }
 }
 
 /* -------------------------------------------------------------------------- */
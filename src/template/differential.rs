@@ -0,0 +1,351 @@
+/// Differential testing: runs two implementations of a part on many generated inputs and reports the
+/// first input they disagree on, minimized. Used by the tests in `tests/differential.rs`, see `cargo diff-test`.
+use std::{
+    cell::Cell,
+    env,
+    fmt::{Debug, Display},
+    panic::{self, AssertUnwindSafe},
+    sync::Once,
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+/// Overrides the random seed, e.g. to reproduce a reported disagreement.
+pub const SEED_ENV_VAR: &str = "AOC_DIFF_SEED";
+/// Overrides the number of generated inputs.
+pub const CASES_ENV_VAR: &str = "AOC_DIFF_CASES";
+
+const DEFAULT_CASES: usize = 1000;
+
+/// A small, seedable pseudo random number generator (SplitMix64) for input generators.
+#[derive(Clone, Debug)]
+pub struct Rng(u64);
+
+impl Rng {
+    pub fn new(seed: u64) -> Self {
+        Rng(seed)
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.0 = self.0.wrapping_add(0x9e37_79b9_7f4a_7c15);
+        let mut z = self.0;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Returns a number in `min..=max`.
+    pub fn range(&mut self, min: u64, max: u64) -> u64 {
+        assert!(min <= max, "invalid range {min}..={max}");
+        match (max - min).checked_add(1) {
+            Some(span) => min + self.next_u64() % span,
+            None => self.next_u64(),
+        }
+    }
+
+    /// Returns `true` with a probability of `p`.
+    pub fn chance(&mut self, p: f64) -> bool {
+        #[allow(clippy::cast_precision_loss)]
+        let sample = (self.next_u64() >> 11) as f64 / (1_u64 << 53) as f64;
+        sample < p
+    }
+
+    /// Returns a random element of `items`.
+    pub fn pick<'a, T>(&mut self, items: &'a [T]) -> &'a T {
+        let index = self.range(0, items.len() as u64 - 1);
+        &items[usize::try_from(index).unwrap()]
+    }
+}
+
+/// The result of running an implementation on an input.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Outcome<T> {
+    Returned(T),
+    Panicked(String),
+}
+
+impl<T: Debug> Display for Outcome<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Outcome::Returned(value) => write!(f, "{value:?}"),
+            Outcome::Panicked(message) => write!(f, "panicked: {message}"),
+        }
+    }
+}
+
+/// An input the two implementations disagree on.
+#[derive(Clone, Debug)]
+pub struct Disagreement<T> {
+    pub seed: u64,
+    /// Index of the generated input the implementations first disagreed on.
+    pub case: usize,
+    /// The generated input.
+    pub original: String,
+    /// The smallest input found that the implementations still disagree on.
+    pub minimized: String,
+    pub subject: Outcome<T>,
+    pub reference: Outcome<T>,
+}
+
+impl<T: Debug> Display for Disagreement<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "implementations disagree on generated input #{}.", self.case)?;
+        writeln!(f, "  subject:   {}", self.subject)?;
+        writeln!(f, "  reference: {}", self.reference)?;
+        writeln!(
+            f,
+            "  minimized input ({} of {} bytes):",
+            self.minimized.len(),
+            self.original.len()
+        )?;
+        for line in self.minimized.lines() {
+            writeln!(f, "    {line}")?;
+        }
+        write!(f, "  reproduce with `{SEED_ENV_VAR}={}`.", self.seed)
+    }
+}
+
+/// Settings of a differential test run.
+#[derive(Clone, Debug)]
+pub struct Differential {
+    pub seed: u64,
+    pub cases: usize,
+}
+
+impl Default for Differential {
+    /// Reads the seed and number of cases from the environment, with a time-based seed otherwise.
+    fn default() -> Self {
+        let seed = env::var(SEED_ENV_VAR)
+            .ok()
+            .and_then(|s| s.trim().parse().ok())
+            .unwrap_or_else(|| {
+                SystemTime::now()
+                    .duration_since(UNIX_EPOCH)
+                    .map_or(0, |d| d.as_nanos() as u64)
+            });
+        let cases = env::var(CASES_ENV_VAR)
+            .ok()
+            .and_then(|s| s.trim().parse().ok())
+            .unwrap_or(DEFAULT_CASES);
+
+        Differential { seed, cases }
+    }
+}
+
+impl Differential {
+    /// Runs `subject` and `reference` on `cases` inputs from `generate`.
+    /// Inputs both implementations panic on are skipped.
+    pub fn check<T: PartialEq + Clone>(
+        &self,
+        generate: impl Fn(&mut Rng) -> String,
+        subject: impl Fn(&str) -> T,
+        reference: impl Fn(&str) -> T,
+    ) -> Result<(), Disagreement<T>> {
+        let compare = |input: &str| {
+            let subject = run(&subject, input);
+            let reference = run(&reference, input);
+            let disagrees = match (&subject, &reference) {
+                (Outcome::Panicked(_), Outcome::Panicked(_)) => false,
+                _ => subject != reference,
+            };
+            disagrees.then_some((subject, reference))
+        };
+
+        let mut rng = Rng::new(self.seed);
+        silence_panics(|| {
+            for case in 0..self.cases {
+                let input = generate(&mut rng);
+                if compare(&input).is_none() {
+                    continue;
+                }
+
+                let minimized = minimize(&input, |candidate| compare(candidate).is_some());
+                let (subject, reference) = compare(&minimized).unwrap();
+                return Err(Disagreement {
+                    seed: self.seed,
+                    case,
+                    original: input,
+                    minimized,
+                    subject,
+                    reference,
+                });
+            }
+            Ok(())
+        })
+    }
+
+    /// Like [`Differential::check`], but panics with a report of the first disagreement.
+    pub fn assert<T: PartialEq + Clone + Debug>(
+        &self,
+        generate: impl Fn(&mut Rng) -> String,
+        subject: impl Fn(&str) -> T,
+        reference: impl Fn(&str) -> T,
+    ) {
+        match self.check(generate, subject, reference) {
+            Ok(()) => println!(
+                "{} generated inputs agree (seed {}).",
+                self.cases, self.seed
+            ),
+            Err(disagreement) => panic!("{disagreement}"),
+        }
+    }
+}
+
+/// Runs `func` on `input`, catching a panic and its message.
+pub fn run<T>(func: impl Fn(&str) -> T, input: &str) -> Outcome<T> {
+    panic::catch_unwind(AssertUnwindSafe(|| func(input))).map_or_else(
+        |payload| {
+            let message = payload
+                .downcast_ref::<&str>()
+                .map(ToString::to_string)
+                .or_else(|| payload.downcast_ref::<String>().cloned())
+                .unwrap_or_default();
+            Outcome::Panicked(message)
+        },
+        Outcome::Returned,
+    )
+}
+
+thread_local! {
+    /// Number of [`silence_panics`] calls running on this thread.
+    static SILENCED: Cell<usize> = const { Cell::new(0) };
+}
+
+static INSTALL_HOOK: Once = Once::new();
+
+/// Expected panics would print a message for every input, so they are silenced while the implementations run.
+/// Only panics on the current thread are silenced, panics on other threads still reach the previous hook.
+pub fn silence_panics<T>(f: impl FnOnce() -> T) -> T {
+    INSTALL_HOOK.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            if SILENCED.with(Cell::get) == 0 {
+                previous(info);
+            }
+        }));
+    });
+
+    let _silenced = Silenced::enter();
+    f()
+}
+
+/// Silences panics on the current thread until dropped, also when `f` panics.
+struct Silenced;
+
+impl Silenced {
+    fn enter() -> Self {
+        SILENCED.with(|count| count.set(count.get() + 1));
+        Silenced
+    }
+}
+
+impl Drop for Silenced {
+    fn drop(&mut self) {
+        SILENCED.with(|count| count.set(count.get() - 1));
+    }
+}
+
+/// Removes chunks of lines, then of characters, as long as `is_failing` holds for the smaller input.
+pub fn minimize(input: &str, is_failing: impl Fn(&str) -> bool) -> String {
+    let by_lines = shrink(
+        input.lines().map(|line| format!("{line}\n")).collect(),
+        &is_failing,
+    );
+    shrink(
+        by_lines.concat().chars().map(String::from).collect(),
+        &is_failing,
+    )
+    .concat()
+}
+
+fn shrink(mut units: Vec<String>, is_failing: &impl Fn(&str) -> bool) -> Vec<String> {
+    let mut chunk = units.len() / 2;
+    while chunk > 0 {
+        let mut reduced = false;
+        let mut start = 0;
+        while start < units.len() {
+            let end = (start + chunk).min(units.len());
+            let candidate: Vec<String> = [&units[..start], &units[end..]].concat();
+            if !candidate.is_empty() && is_failing(&candidate.concat()) {
+                units = candidate;
+                reduced = true;
+            } else {
+                start = end;
+            }
+        }
+
+        if !reduced {
+            chunk /= 2;
+        }
+    }
+    units
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::{cell::Cell, panic};
+
+    use super::{minimize, silence_panics, Differential, Outcome, Rng, SILENCED};
+
+    #[test]
+    fn silences_panics_only_while_running() {
+        assert!(panic::catch_unwind(|| silence_panics(|| panic!("expected"))).is_err());
+        assert_eq!(SILENCED.with(Cell::get), 0);
+        assert_eq!(silence_panics(|| SILENCED.with(Cell::get)), 1);
+
+        let other = std::thread::spawn(|| SILENCED.with(Cell::get));
+        assert_eq!(silence_panics(|| other.join().unwrap()), 0);
+    }
+
+    #[test]
+    fn generates_reproducibly() {
+        let mut a = Rng::new(7);
+        let mut b = Rng::new(7);
+        for _ in 0..100 {
+            let n = a.range(3, 9);
+            assert_eq!(n, b.range(3, 9));
+            assert!((3..=9).contains(&n));
+        }
+        assert_eq!(Rng::new(1).range(5, 5), 5);
+        assert_eq!(*Rng::new(1).pick(&['x']), 'x');
+    }
+
+    #[test]
+    fn minimizes_inputs() {
+        let input = "1 2 3\n4 5 6\n7 8 9\n";
+        assert_eq!(minimize(input, |s| s.contains('5')), "5");
+        assert_eq!(minimize(input, |s| s.contains('4') && s.contains('9')), "49");
+    }
+
+    #[test]
+    fn finds_disagreements() {
+        let differential = Differential {
+            seed: 42,
+            cases: 200,
+        };
+        let generate = |rng: &mut Rng| {
+            (0..rng.range(1, 20))
+                .map(|_| rng.range(0, 9).to_string())
+                .collect::<Vec<_>>()
+                .join(" ")
+        };
+        let sum = |s: &str| s.split(' ').map(|n| n.parse::<u64>().unwrap()).sum::<u64>();
+
+        assert!(differential.check(generate, sum, sum).is_ok());
+
+        let disagreement = differential
+            .check(
+                generate,
+                |s| sum(&s.replace('7', "0")),
+                |s| {
+                    assert!(!s.is_empty(), "empty input");
+                    sum(s)
+                },
+            )
+            .unwrap_err();
+        assert_eq!(disagreement.minimized, "7");
+        assert_eq!(disagreement.subject, Outcome::Returned(0));
+        assert_eq!(disagreement.reference, Outcome::Returned(7));
+    }
+}
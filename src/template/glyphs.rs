@@ -0,0 +1,284 @@
+/// Recognizes answers that puzzles draw as block letters, e.g. for 2022 day 10 or 2021 day 13.
+///
+/// Supports the two fonts used by Advent of Code: letters 4 pixels wide and 6 pixels tall,
+/// and letters 6 pixels wide and 10 pixels tall. Lit pixels are `#` or `█`, every other character is dark.
+use std::fmt::Display;
+
+const FONT_6: [(char, &str); 18] = [
+    ('A', ".##.\n#..#\n#..#\n####\n#..#\n#..#"),
+    ('B', "###.\n#..#\n###.\n#..#\n#..#\n###."),
+    ('C', ".##.\n#..#\n#...\n#...\n#..#\n.##."),
+    ('E', "####\n#...\n###.\n#...\n#...\n####"),
+    ('F', "####\n#...\n###.\n#...\n#...\n#..."),
+    ('G', ".##.\n#..#\n#...\n#.##\n#..#\n.###"),
+    ('H', "#..#\n#..#\n####\n#..#\n#..#\n#..#"),
+    ('I', ".###\n..#.\n..#.\n..#.\n..#.\n.###"),
+    ('J', "..##\n...#\n...#\n...#\n#..#\n.##."),
+    ('K', "#..#\n#.#.\n##..\n#.#.\n#.#.\n#..#"),
+    ('L', "#...\n#...\n#...\n#...\n#...\n####"),
+    ('O', ".##.\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('P', "###.\n#..#\n#..#\n###.\n#...\n#..."),
+    ('R', "###.\n#..#\n#..#\n###.\n#.#.\n#..#"),
+    ('S', ".###\n#...\n#...\n.##.\n...#\n###."),
+    ('U', "#..#\n#..#\n#..#\n#..#\n#..#\n.##."),
+    ('Y', "#...\n#...\n.#.#\n..#.\n..#.\n..#."),
+    ('Z', "####\n...#\n..#.\n.#..\n#...\n####"),
+];
+
+const FONT_10: [(char, &str); 15] = [
+    (
+        'A',
+        "..##..\n.#..#.\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'B',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#....#\n#....#\n#....#\n#....#\n#####.",
+    ),
+    (
+        'C',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#....#\n.####.",
+    ),
+    (
+        'E',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'F',
+        "######\n#.....\n#.....\n#.....\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'G',
+        ".####.\n#....#\n#.....\n#.....\n#.....\n#..###\n#....#\n#....#\n#...##\n.###.#",
+    ),
+    (
+        'H',
+        "#....#\n#....#\n#....#\n#....#\n######\n#....#\n#....#\n#....#\n#....#\n#....#",
+    ),
+    (
+        'J',
+        "...###\n....#.\n....#.\n....#.\n....#.\n....#.\n....#.\n#...#.\n#...#.\n.###..",
+    ),
+    (
+        'K',
+        "#....#\n#...#.\n#..#..\n#.#...\n##....\n##....\n#.#...\n#..#..\n#...#.\n#....#",
+    ),
+    (
+        'L',
+        "#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n#.....\n######",
+    ),
+    (
+        'N',
+        "#....#\n##...#\n##...#\n#.#..#\n#.#..#\n#..#.#\n#..#.#\n#...##\n#...##\n#....#",
+    ),
+    (
+        'P',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#.....\n#.....\n#.....\n#.....\n#.....",
+    ),
+    (
+        'R',
+        "#####.\n#....#\n#....#\n#....#\n#####.\n#..#..\n#...#.\n#...#.\n#....#\n#....#",
+    ),
+    (
+        'X',
+        "#....#\n#....#\n.#..#.\n.#..#.\n..##..\n..##..\n.#..#.\n.#..#.\n#....#\n#....#",
+    ),
+    (
+        'Z',
+        "######\n.....#\n.....#\n....#.\n...#..\n..#...\n.#....\n#.....\n#.....\n######",
+    ),
+];
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum GlyphError {
+    Empty,
+    UnsupportedHeight(usize),
+    UnknownGlyph { position: usize, pattern: String },
+}
+
+impl Display for GlyphError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            GlyphError::Empty => write!(f, "no lit pixels found."),
+            GlyphError::UnsupportedHeight(h) => {
+                write!(f, "letters are {h} pixels tall, expected 6 or 10.")
+            }
+            GlyphError::UnknownGlyph { position, pattern } => {
+                write!(f, "unknown glyph at position {position}:\n{pattern}")
+            }
+        }
+    }
+}
+
+fn is_lit(c: char) -> bool {
+    c == '#' || c == '█'
+}
+
+/// Returns `true` if `result` looks like rendered letters: multiple lines of lit and dark pixels.
+pub fn is_letter_art(result: &str) -> bool {
+    result.trim().contains('\n')
+        && result.chars().any(is_lit)
+        && result
+            .chars()
+            .all(|c| is_lit(c) || matches!(c, '.' | ' ' | '\n' | '\r'))
+}
+
+/// Turns a rendered grid of block letters into a string, e.g. `"EHZ"`.
+pub fn recognize(rendered: &str) -> Result<String, GlyphError> {
+    let mut rows: Vec<Vec<bool>> = rendered
+        .lines()
+        .map(|line| line.chars().map(is_lit).collect())
+        .collect();
+
+    // trim dark rows at the top and bottom.
+    while rows.last().is_some_and(|r| !r.contains(&true)) {
+        rows.pop();
+    }
+    let first_lit = rows
+        .iter()
+        .position(|r| r.contains(&true))
+        .ok_or(GlyphError::Empty)?;
+    rows.drain(..first_lit);
+
+    // letters sit on a fixed grid: a glyph followed by a dark gap, starting from column 0.
+    let (font, width, spacing): (&[(char, &str)], usize, usize) = match rows.len() {
+        6 => (&FONT_6, 4, 5),
+        10 => (&FONT_10, 6, 8),
+        h => return Err(GlyphError::UnsupportedHeight(h)),
+    };
+
+    let columns = rows
+        .iter()
+        .filter_map(|r| r.iter().rposition(|&lit| lit))
+        .max()
+        .map_or(0, |last| last + 1);
+
+    let mut letters = String::new();
+
+    for x in (0..columns).step_by(spacing) {
+        let pattern: Vec<String> = rows
+            .iter()
+            .map(|r| {
+                (x..x + width)
+                    .map(|i| {
+                        if r.get(i).copied().unwrap_or(false) {
+                            '#'
+                        } else {
+                            '.'
+                        }
+                    })
+                    .collect()
+            })
+            .collect();
+        let pattern = pattern.join("\n");
+
+        let letter = font
+            .iter()
+            .find(|(_, glyph)| *glyph == pattern)
+            .map(|(letter, _)| *letter)
+            .ok_or_else(|| GlyphError::UnknownGlyph {
+                position: letters.len(),
+                pattern: pattern.clone(),
+            })?;
+
+        letters.push(letter);
+    }
+
+    Ok(letters)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{is_letter_art, recognize, GlyphError, FONT_10, FONT_6};
+
+    #[test]
+    fn recognizes_small_font() {
+        // 2022 day 10.
+        let rendered = [
+            "####.#..#.###..####.###....##..##..#....",
+            "#....#..#.#..#....#.#..#....#.#..#.#....",
+            "###..####.#..#...#..#..#....#.#....#....",
+            "#....#..#.###...#...###.....#.#.##.#....",
+            "#....#..#.#....#....#....#..#.#..#.#....",
+            "####.#..#.#....####.#.....##...###.####.",
+        ]
+        .join("\n");
+        assert_eq!(recognize(&rendered).unwrap(), "EHPZPJGL");
+    }
+
+    #[test]
+    fn recognizes_block_characters() {
+        let rendered = [
+            " ██  ███  ",
+            "█  █ █  █ ",
+            "█  █ ███  ",
+            "████ █  █ ",
+            "█  █ █  █ ",
+            "█  █ ███  ",
+        ]
+        .join("\n");
+        assert_eq!(recognize(&rendered).unwrap(), "AB");
+    }
+
+    #[test]
+    fn splits_letters_on_fixed_spacing() {
+        // `I` starts with a dark column and `Y` lights up its gap column.
+        let rendered = [
+            "#..#..###.#...##..#.",
+            "#..#...#..#...##..#.",
+            "####...#...#.#.####.",
+            "#..#...#....#..#..#.",
+            "#..#...#....#..#..#.",
+            "#..#..###...#..#..#.",
+        ]
+        .join("\n");
+        assert_eq!(recognize(&rendered).unwrap(), "HIYH");
+    }
+
+    #[test]
+    fn recognizes_large_font() {
+        let letters = ['H', 'N', 'Z'];
+        let glyphs: Vec<Vec<&str>> = letters
+            .iter()
+            .map(|l| {
+                FONT_10
+                    .iter()
+                    .find(|(c, _)| c == l)
+                    .unwrap()
+                    .1
+                    .lines()
+                    .collect()
+            })
+            .collect();
+        let rendered = (0..10)
+            .map(|row| glyphs.iter().map(|g| g[row]).collect::<Vec<_>>().join(".."))
+            .collect::<Vec<_>>()
+            .join("\n");
+        assert_eq!(recognize(&format!("\n{rendered}\n\n")).unwrap(), "HNZ");
+    }
+
+    #[test]
+    fn recognizes_every_glyph() {
+        for (letter, glyph) in FONT_6.iter().chain(FONT_10.iter()) {
+            assert_eq!(recognize(glyph).unwrap(), letter.to_string());
+        }
+    }
+
+    #[test]
+    fn errors_for_unknown_glyphs() {
+        let rendered = "####\n####\n####\n####\n####\n####";
+        assert!(matches!(
+            recognize(rendered),
+            Err(GlyphError::UnknownGlyph { position: 0, .. })
+        ));
+        assert_eq!(recognize("#\n#\n#"), Err(GlyphError::UnsupportedHeight(3)));
+    }
+
+    #[test]
+    fn detects_letter_art() {
+        assert!(is_letter_art("#.#\n.#.\n"));
+        assert!(!is_letter_art("42"));
+        assert!(!is_letter_art("1\n2"));
+    }
+}
@@ -0,0 +1,817 @@
+/// Exports grids as pictures: PPM, PNG and animated GIF.
+///
+/// An [`Image`] is a palette-indexed pixel buffer. Build one from the grid types the solutions use,
+/// e.g. [`Image::from_bool_grid`], then write it with [`Image::save`] or collect several into an [`Animation`].
+///
+/// ```ignore
+/// let image = Image::from_bool_grid(&grid, Rgb::BLACK, Rgb::GREEN, 4);
+/// image.save("target/14.png")?;
+/// ```
+use std::{collections::HashMap, fmt::Display, fs, path::Path, time::Duration};
+
+use ndarray::Array2;
+
+use crate::template::visualize::Frame;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Rgb(pub u8, pub u8, pub u8);
+
+impl Rgb {
+    pub const BLACK: Rgb = Rgb(0, 0, 0);
+    pub const WHITE: Rgb = Rgb(255, 255, 255);
+    pub const GRAY: Rgb = Rgb(128, 128, 128);
+    pub const RED: Rgb = Rgb(204, 51, 51);
+    pub const GREEN: Rgb = Rgb(0, 153, 0);
+    pub const GOLD: Rgb = Rgb(255, 255, 102);
+    pub const BLUE: Rgb = Rgb(15, 15, 35);
+}
+
+/// Returns `steps` colors from `from` to `to`, e.g. for the heights of a topographic map.
+pub fn gradient(from: Rgb, to: Rgb, steps: usize) -> Vec<Rgb> {
+    let lerp = |a: u8, b: u8, i: usize| {
+        let t = if steps > 1 {
+            i as f64 / (steps - 1) as f64
+        } else {
+            0.0
+        };
+        (f64::from(a) + (f64::from(b) - f64::from(a)) * t).round() as u8
+    };
+
+    (0..steps)
+        .map(|i| {
+            Rgb(
+                lerp(from.0, to.0, i),
+                lerp(from.1, to.1, i),
+                lerp(from.2, to.2, i),
+            )
+        })
+        .collect()
+}
+
+#[derive(Debug)]
+pub enum ImageError {
+    Empty,
+    TooManyColors(usize),
+    SizeMismatch {
+        expected: (usize, usize),
+        actual: (usize, usize),
+    },
+    UnsupportedFormat(String),
+    IO(std::io::Error),
+}
+
+impl Display for ImageError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ImageError::Empty => write!(f, "image has no pixels or animation has no frames."),
+            ImageError::TooManyColors(n) => {
+                write!(f, "{n} colors used, PNG and GIF support at most 256.")
+            }
+            ImageError::SizeMismatch { expected, actual } => write!(
+                f,
+                "frame is {}x{}, expected {}x{}.",
+                actual.0, actual.1, expected.0, expected.1
+            ),
+            ImageError::UnsupportedFormat(ext) => {
+                write!(
+                    f,
+                    "unsupported file extension \"{ext}\", expected ppm, png or gif."
+                )
+            }
+            ImageError::IO(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<std::io::Error> for ImageError {
+    fn from(e: std::io::Error) -> Self {
+        ImageError::IO(e)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Collects distinct colors and hands out their palette index.
+#[derive(Default)]
+struct PaletteBuilder {
+    colors: Vec<Rgb>,
+    indices: HashMap<Rgb, usize>,
+}
+
+impl PaletteBuilder {
+    fn index(&mut self, color: Rgb) -> usize {
+        *self.indices.entry(color).or_insert_with(|| {
+            self.colors.push(color);
+            self.colors.len() - 1
+        })
+    }
+}
+
+/// A palette-indexed image with at most 256 colors. `x` is the column and `y` the row of a pixel.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Image {
+    pub width: usize,
+    pub height: usize,
+    palette: Vec<Rgb>,
+    pixels: Vec<u8>,
+}
+
+impl Image {
+    /// Creates an image filled with `background`.
+    pub fn new(width: usize, height: usize, background: Rgb) -> Self {
+        Self {
+            width,
+            height,
+            palette: vec![background],
+            pixels: vec![0; width * height],
+        }
+    }
+
+    /// Creates an image by calling `f(x, y)` for every pixel, each pixel scaled up to `scale` x `scale`.
+    ///
+    /// # Panics
+    /// Panics if `f` returns more than 256 distinct colors.
+    pub fn from_fn(
+        width: usize,
+        height: usize,
+        scale: usize,
+        f: impl Fn(usize, usize) -> Rgb,
+    ) -> Self {
+        let scale = scale.max(1);
+        let mut palette = PaletteBuilder::default();
+        let mut indices = Vec::with_capacity(width * height);
+
+        for y in 0..height {
+            for x in 0..width {
+                let index = palette.index(f(x, y));
+                assert!(index < 256, "images support at most 256 colors.");
+                indices.push(index as u8);
+            }
+        }
+
+        let mut pixels = Vec::with_capacity(width * height * scale * scale);
+        for row in indices.chunks(width.max(1)).take(height) {
+            let scaled_row: Vec<u8> = row
+                .iter()
+                .flat_map(|i| std::iter::repeat_n(*i, scale))
+                .collect();
+            for _ in 0..scale {
+                pixels.extend_from_slice(&scaled_row);
+            }
+        }
+
+        Self {
+            width: width * scale,
+            height: height * scale,
+            palette: palette.colors,
+            pixels,
+        }
+    }
+
+    /// Draws `on` for `true` and `off` for `false` cells.
+    pub fn from_bool_grid(grid: &Array2<bool>, off: Rgb, on: Rgb, scale: usize) -> Self {
+        let (rows, columns) = grid.dim();
+        Self::from_fn(
+            columns,
+            rows,
+            scale,
+            |x, y| if grid[(y, x)] { on } else { off },
+        )
+    }
+
+    /// Uses each cell as an index into `colors`. Values past the end use the last color.
+    ///
+    /// # Panics
+    /// Panics if `colors` is empty.
+    pub fn from_u8_grid(grid: &Array2<u8>, colors: &[Rgb], scale: usize) -> Self {
+        assert!(!colors.is_empty(), "at least one color is required.");
+        let (rows, columns) = grid.dim();
+        Self::from_fn(columns, rows, scale, |x, y| {
+            colors[(grid[(y, x)] as usize).min(colors.len() - 1)]
+        })
+    }
+
+    /// Looks up the color of each cell in `colors`, cells without a mapping use `default`.
+    pub fn from_char_grid(
+        grid: &Array2<char>,
+        colors: &[(char, Rgb)],
+        default: Rgb,
+        scale: usize,
+    ) -> Self {
+        let colors: HashMap<char, Rgb> = colors.iter().copied().collect();
+        let (rows, columns) = grid.dim();
+        Self::from_fn(columns, rows, scale, |x, y| {
+            colors.get(&grid[(y, x)]).copied().unwrap_or(default)
+        })
+    }
+
+    /// Draws a visualization frame, including its annotated points, like [`Image::from_char_grid`].
+    pub fn from_frame(frame: &Frame, colors: &[(char, Rgb)], default: Rgb, scale: usize) -> Self {
+        let cells = frame.composed();
+        let colors: HashMap<char, Rgb> = colors.iter().copied().collect();
+        Self::from_fn(frame.width, frame.height, scale, |x, y| {
+            colors
+                .get(&cells[y * frame.width + x])
+                .copied()
+                .unwrap_or(default)
+        })
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<Rgb> {
+        (x < self.width && y < self.height)
+            .then(|| self.palette[self.pixels[y * self.width + x] as usize])
+    }
+
+    /// Sets a pixel. Coordinates outside of the image are ignored.
+    ///
+    /// # Panics
+    /// Panics if this adds a 257th color.
+    pub fn set(&mut self, x: usize, y: usize, color: Rgb) {
+        if x >= self.width || y >= self.height {
+            return;
+        }
+
+        let index = match self.palette.iter().position(|c| *c == color) {
+            Some(index) => index,
+            None => {
+                assert!(
+                    self.palette.len() < 256,
+                    "images support at most 256 colors."
+                );
+                self.palette.push(color);
+                self.palette.len() - 1
+            }
+        };
+
+        self.pixels[y * self.width + x] = index as u8;
+    }
+
+    pub fn palette(&self) -> &[Rgb] {
+        &self.palette
+    }
+
+    /// Encodes the image as binary PPM (`P6`).
+    pub fn to_ppm(&self) -> Vec<u8> {
+        let mut out = format!("P6\n{} {}\n255\n", self.width, self.height).into_bytes();
+        for index in &self.pixels {
+            let Rgb(r, g, b) = self.palette[*index as usize];
+            out.extend_from_slice(&[r, g, b]);
+        }
+        out
+    }
+
+    /// Encodes the image as an indexed-color PNG.
+    pub fn to_png(&self) -> Result<Vec<u8>, ImageError> {
+        self.check_size()?;
+        Ok(png::encode(self))
+    }
+
+    /// Encodes the image as a single-frame GIF.
+    pub fn to_gif(&self) -> Result<Vec<u8>, ImageError> {
+        let mut animation = Animation::new(Duration::ZERO);
+        animation.push(self)?;
+        animation.to_gif()
+    }
+
+    /// Writes the image in the format matching the extension of `path`: `ppm`, `png` or `gif`.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), ImageError> {
+        let path = path.as_ref();
+        let bytes = match extension(path).as_str() {
+            "ppm" => {
+                self.check_size()?;
+                self.to_ppm()
+            }
+            "png" => self.to_png()?,
+            "gif" => self.to_gif()?,
+            ext => return Err(ImageError::UnsupportedFormat(ext.into())),
+        };
+        write(path, &bytes)
+    }
+
+    fn check_size(&self) -> Result<(), ImageError> {
+        if self.pixels.is_empty() {
+            Err(ImageError::Empty)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+/// A sequence of equally sized images, exported as a looping GIF.
+#[derive(Clone, Debug)]
+pub struct Animation {
+    delay: Duration,
+    size: Option<(usize, usize)>,
+    palette: PaletteIndex,
+    frames: Vec<Vec<u8>>,
+}
+
+#[derive(Clone, Debug, Default)]
+struct PaletteIndex {
+    colors: Vec<Rgb>,
+    indices: HashMap<Rgb, u8>,
+}
+
+impl Animation {
+    /// Creates an empty animation that shows each frame for `delay`.
+    pub fn new(delay: Duration) -> Self {
+        Self {
+            delay,
+            size: None,
+            palette: PaletteIndex::default(),
+            frames: vec![],
+        }
+    }
+
+    /// Appends a frame. All frames must have the same size and share at most 256 colors in total.
+    pub fn push(&mut self, image: &Image) -> Result<(), ImageError> {
+        let size = (image.width, image.height);
+        if let Some(expected) = self.size {
+            if expected != size {
+                return Err(ImageError::SizeMismatch {
+                    expected,
+                    actual: size,
+                });
+            }
+        }
+
+        let mut mapping = Vec::with_capacity(image.palette.len());
+        for color in &image.palette {
+            let index = match self.palette.indices.get(color) {
+                Some(index) => *index,
+                None => {
+                    if self.palette.colors.len() == 256 {
+                        return Err(ImageError::TooManyColors(257));
+                    }
+                    self.palette.colors.push(*color);
+                    let index = (self.palette.colors.len() - 1) as u8;
+                    self.palette.indices.insert(*color, index);
+                    index
+                }
+            };
+            mapping.push(index);
+        }
+
+        self.size = Some(size);
+        self.frames
+            .push(image.pixels.iter().map(|i| mapping[*i as usize]).collect());
+        Ok(())
+    }
+
+    pub fn len(&self) -> usize {
+        self.frames.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.frames.is_empty()
+    }
+
+    /// Encodes all frames as an animated GIF that loops forever.
+    pub fn to_gif(&self) -> Result<Vec<u8>, ImageError> {
+        match self.size {
+            Some((width, height)) if width > 0 && height > 0 => {
+                if width > u16::MAX as usize || height > u16::MAX as usize {
+                    return Err(ImageError::SizeMismatch {
+                        expected: (u16::MAX as usize, u16::MAX as usize),
+                        actual: (width, height),
+                    });
+                }
+                Ok(gif::encode(
+                    width as u16,
+                    height as u16,
+                    &self.palette.colors,
+                    &self.frames,
+                    self.delay,
+                ))
+            }
+            _ => Err(ImageError::Empty),
+        }
+    }
+
+    /// Writes the animation as GIF to `path`.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), ImageError> {
+        let path = path.as_ref();
+        match extension(path).as_str() {
+            "gif" => write(path, &self.to_gif()?),
+            ext => Err(ImageError::UnsupportedFormat(ext.into())),
+        }
+    }
+}
+
+fn extension(path: &Path) -> String {
+    path.extension()
+        .map(|e| e.to_string_lossy().to_lowercase())
+        .unwrap_or_default()
+}
+
+fn write(path: &Path, bytes: &[u8]) -> Result<(), ImageError> {
+    if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, bytes)?;
+    Ok(())
+}
+
+/* -------------------------------------------------------------------------- */
+
+mod png {
+    use super::Image;
+
+    const SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1a, b'\n'];
+    const MAX_STORED_BLOCK: usize = 0xffff;
+
+    pub fn encode(image: &Image) -> Vec<u8> {
+        let mut out = SIGNATURE.to_vec();
+
+        let mut header = vec![];
+        header.extend_from_slice(&(image.width as u32).to_be_bytes());
+        header.extend_from_slice(&(image.height as u32).to_be_bytes());
+        // bit depth 8, indexed color, deflate, adaptive filtering, no interlace.
+        header.extend_from_slice(&[8, 3, 0, 0, 0]);
+        chunk(&mut out, b"IHDR", &header);
+
+        let palette: Vec<u8> = image.palette.iter().flat_map(|c| [c.0, c.1, c.2]).collect();
+        chunk(&mut out, b"PLTE", &palette);
+
+        // every scanline starts with filter type 0 (none).
+        let mut raw = Vec::with_capacity((image.width + 1) * image.height);
+        for row in image.pixels.chunks(image.width) {
+            raw.push(0);
+            raw.extend_from_slice(row);
+        }
+        chunk(&mut out, b"IDAT", &zlib_stored(&raw));
+
+        chunk(&mut out, b"IEND", &[]);
+        out
+    }
+
+    fn chunk(out: &mut Vec<u8>, kind: &[u8; 4], data: &[u8]) {
+        out.extend_from_slice(&(data.len() as u32).to_be_bytes());
+        let start = out.len();
+        out.extend_from_slice(kind);
+        out.extend_from_slice(data);
+        let crc = crc32(&out[start..]);
+        out.extend_from_slice(&crc.to_be_bytes());
+    }
+
+    /// Wraps `data` in a zlib stream made of uncompressed deflate blocks.
+    pub(super) fn zlib_stored(data: &[u8]) -> Vec<u8> {
+        let mut out = vec![0x78, 0x01];
+        let mut blocks = data.chunks(MAX_STORED_BLOCK).peekable();
+
+        if blocks.peek().is_none() {
+            out.extend_from_slice(&[1, 0, 0, 0xff, 0xff]);
+        }
+
+        while let Some(block) = blocks.next() {
+            let is_final = blocks.peek().is_none();
+            let len = block.len() as u16;
+            out.push(u8::from(is_final));
+            out.extend_from_slice(&len.to_le_bytes());
+            out.extend_from_slice(&(!len).to_le_bytes());
+            out.extend_from_slice(block);
+        }
+
+        out.extend_from_slice(&adler32(data).to_be_bytes());
+        out
+    }
+
+    pub(super) fn crc32(data: &[u8]) -> u32 {
+        let mut crc = 0xffff_ffff_u32;
+        for byte in data {
+            crc ^= u32::from(*byte);
+            for _ in 0..8 {
+                crc = if crc & 1 == 1 {
+                    (crc >> 1) ^ 0xedb8_8320
+                } else {
+                    crc >> 1
+                };
+            }
+        }
+        !crc
+    }
+
+    pub(super) fn adler32(data: &[u8]) -> u32 {
+        let (mut a, mut b) = (1_u32, 0_u32);
+        for byte in data {
+            a = (a + u32::from(*byte)) % 65521;
+            b = (b + a) % 65521;
+        }
+        (b << 16) | a
+    }
+}
+
+mod gif {
+    use std::{collections::HashMap, time::Duration};
+
+    use super::Rgb;
+
+    const MAX_CODE: u16 = 4096;
+
+    pub fn encode(
+        width: u16,
+        height: u16,
+        palette: &[Rgb],
+        frames: &[Vec<u8>],
+        delay: Duration,
+    ) -> Vec<u8> {
+        // the color table size must be a power of two, at least 2 entries.
+        let table_bits = (1..=8).find(|b| 1 << b >= palette.len()).unwrap_or(8);
+        let min_code_size = table_bits.max(2) as u8;
+
+        let mut out = b"GIF89a".to_vec();
+        out.extend_from_slice(&width.to_le_bytes());
+        out.extend_from_slice(&height.to_le_bytes());
+        out.push(0x80 | 0x70 | (table_bits as u8 - 1));
+        out.extend_from_slice(&[0, 0]);
+
+        for i in 0..1 << table_bits {
+            let Rgb(r, g, b) = palette.get(i).copied().unwrap_or(Rgb::BLACK);
+            out.extend_from_slice(&[r, g, b]);
+        }
+
+        if frames.len() > 1 {
+            // NETSCAPE2.0 extension: loop forever.
+            out.extend_from_slice(&[0x21, 0xff, 0x0b]);
+            out.extend_from_slice(b"NETSCAPE2.0");
+            out.extend_from_slice(&[0x03, 0x01, 0x00, 0x00, 0x00]);
+        }
+
+        let delay_cs = (delay.as_millis() / 10).min(u16::MAX as u128) as u16;
+
+        for frame in frames {
+            // graphic control extension with the frame delay.
+            out.extend_from_slice(&[0x21, 0xf9, 0x04, 0x00]);
+            out.extend_from_slice(&delay_cs.to_le_bytes());
+            out.extend_from_slice(&[0x00, 0x00]);
+
+            // image descriptor covering the whole canvas, no local color table.
+            out.push(0x2c);
+            out.extend_from_slice(&[0, 0, 0, 0]);
+            out.extend_from_slice(&width.to_le_bytes());
+            out.extend_from_slice(&height.to_le_bytes());
+            out.push(0);
+
+            out.push(min_code_size);
+            for block in lzw(frame, min_code_size).chunks(255) {
+                out.push(block.len() as u8);
+                out.extend_from_slice(block);
+            }
+            out.push(0);
+        }
+
+        out.push(0x3b);
+        out
+    }
+
+    struct BitWriter {
+        bytes: Vec<u8>,
+        buffer: u32,
+        bits: u8,
+    }
+
+    impl BitWriter {
+        fn write(&mut self, code: u16, size: u8) {
+            self.buffer |= u32::from(code) << self.bits;
+            self.bits += size;
+            while self.bits >= 8 {
+                self.bytes.push(self.buffer as u8);
+                self.buffer >>= 8;
+                self.bits -= 8;
+            }
+        }
+
+        fn finish(mut self) -> Vec<u8> {
+            if self.bits > 0 {
+                self.bytes.push(self.buffer as u8);
+            }
+            self.bytes
+        }
+    }
+
+    /// Compresses palette indices with the variable-length LZW flavour used by GIF.
+    pub(super) fn lzw(indices: &[u8], min_code_size: u8) -> Vec<u8> {
+        let clear = 1_u16 << min_code_size;
+        let end = clear + 1;
+
+        let mut writer = BitWriter {
+            bytes: vec![],
+            buffer: 0,
+            bits: 0,
+        };
+        let mut table: HashMap<(u16, u8), u16> = HashMap::new();
+        let mut code_size = min_code_size + 1;
+        let mut next_code = end + 1;
+
+        writer.write(clear, code_size);
+
+        let Some((first, rest)) = indices.split_first() else {
+            writer.write(end, code_size);
+            return writer.finish();
+        };
+
+        let mut prefix = u16::from(*first);
+
+        for index in rest {
+            if let Some(code) = table.get(&(prefix, *index)) {
+                prefix = *code;
+                continue;
+            }
+
+            writer.write(prefix, code_size);
+
+            if next_code < MAX_CODE {
+                table.insert((prefix, *index), next_code);
+                next_code += 1;
+                if next_code > 1 << code_size {
+                    code_size += 1;
+                }
+            } else {
+                writer.write(clear, code_size);
+                table.clear();
+                code_size = min_code_size + 1;
+                next_code = end + 1;
+            }
+
+            prefix = u16::from(*index);
+        }
+
+        writer.write(prefix, code_size);
+        // the decoder adds one more entry after reading the last code.
+        if next_code == 1 << code_size && code_size < 12 {
+            code_size += 1;
+        }
+        writer.write(end, code_size);
+        writer.finish()
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use ndarray::{array, Array2};
+
+    use super::{gif, gradient, png, Animation, Image, ImageError, Rgb};
+    use crate::template::visualize::{Frame, Point};
+
+    /// Reference GIF LZW decoder, used to check the encoder.
+    fn lzw_decode(data: &[u8], min_code_size: u8) -> Vec<u8> {
+        let clear = 1_usize << min_code_size;
+        let end = clear + 1;
+        let reset = || -> Vec<Vec<u8>> {
+            let mut table: Vec<Vec<u8>> = (0..clear).map(|i| vec![i as u8]).collect();
+            table.extend([vec![], vec![]]);
+            table
+        };
+
+        let mut table = reset();
+        let mut code_size = min_code_size + 1;
+        let (mut pos, mut out, mut prev): (usize, Vec<u8>, Option<Vec<u8>>) = (0, vec![], None);
+
+        loop {
+            let mut code = 0_usize;
+            for i in 0..code_size as usize {
+                let bit = (data[(pos + i) / 8] >> ((pos + i) % 8)) & 1;
+                code |= (bit as usize) << i;
+            }
+            pos += code_size as usize;
+
+            if code == clear {
+                table = reset();
+                code_size = min_code_size + 1;
+                prev = None;
+                continue;
+            }
+            if code == end {
+                return out;
+            }
+
+            let entry = match (table.get(code), &prev) {
+                (Some(entry), _) => entry.clone(),
+                (None, Some(p)) => [p.clone(), vec![p[0]]].concat(),
+                (None, None) => panic!("invalid code {code}"),
+            };
+            if let Some(p) = prev {
+                if table.len() < 4096 {
+                    table.push([p, vec![entry[0]]].concat());
+                    if table.len() == 1 << code_size && code_size < 12 {
+                        code_size += 1;
+                    }
+                }
+            }
+            out.extend_from_slice(&entry);
+            prev = Some(entry);
+        }
+    }
+
+    #[test]
+    fn converts_grids() {
+        let bools = array![[true, false], [false, true]];
+        let image = Image::from_bool_grid(&bools, Rgb::BLACK, Rgb::WHITE, 2);
+        assert_eq!((image.width, image.height), (4, 4));
+        assert_eq!(image.get(1, 1), Some(Rgb::WHITE));
+        assert_eq!(image.get(2, 1), Some(Rgb::BLACK));
+        assert_eq!(image.get(3, 3), Some(Rgb::WHITE));
+        assert_eq!(image.get(4, 0), None);
+
+        let heights: Array2<u8> = array![[0, 9, 12]];
+        let colors = gradient(Rgb::BLACK, Rgb::WHITE, 10);
+        let image = Image::from_u8_grid(&heights, &colors, 1);
+        assert_eq!(image.get(0, 0), Some(Rgb::BLACK));
+        assert_eq!(image.get(1, 0), Some(Rgb::WHITE));
+        assert_eq!(image.get(2, 0), Some(Rgb::WHITE));
+
+        let chars = array![['#', '.', 'X']];
+        let image = Image::from_char_grid(&chars, &[('#', Rgb::RED)], Rgb::GRAY, 1);
+        assert_eq!(image.get(0, 0), Some(Rgb::RED));
+        assert_eq!(image.get(2, 0), Some(Rgb::GRAY));
+        assert_eq!(image.palette().len(), 2);
+    }
+
+    #[test]
+    fn converts_frames() {
+        let frame = Frame::new(3, 1, '.').with_point(Point::new(1, 0, '^'));
+        let image = Image::from_frame(&frame, &[('^', Rgb::GOLD)], Rgb::BLUE, 1);
+        assert_eq!(image.get(0, 0), Some(Rgb::BLUE));
+        assert_eq!(image.get(1, 0), Some(Rgb::GOLD));
+    }
+
+    #[test]
+    fn encodes_ppm() {
+        let mut image = Image::new(2, 1, Rgb::BLACK);
+        image.set(1, 0, Rgb(1, 2, 3));
+        assert_eq!(image.to_ppm(), b"P6\n2 1\n255\n\x00\x00\x00\x01\x02\x03");
+    }
+
+    #[test]
+    fn computes_checksums() {
+        assert_eq!(png::crc32(b"IEND"), 0xae42_6082);
+        assert_eq!(png::crc32(b"123456789"), 0xcbf4_3926);
+        assert_eq!(png::adler32(b"Wikipedia"), 0x11e6_0398);
+    }
+
+    #[test]
+    fn encodes_png() {
+        let grid = Array2::from_shape_fn((3, 5), |(y, x)| (x + y) % 2 == 0);
+        let png = Image::from_bool_grid(&grid, Rgb::BLACK, Rgb::GREEN, 1)
+            .to_png()
+            .unwrap();
+        assert_eq!(&png[..8], b"\x89PNG\r\n\x1a\n");
+        assert_eq!(&png[12..16], b"IHDR");
+        assert_eq!(&png[16..24], &[0, 0, 0, 5, 0, 0, 0, 3]);
+        assert!(png.ends_with(b"IEND\xae\x42\x60\x82"));
+
+        let zlib = png::zlib_stored(&vec![7; 70_000]);
+        // two stored blocks: header, two block headers, data, adler32.
+        assert_eq!(zlib.len(), 2 + 5 + 5 + 70_000 + 4);
+        assert_eq!(zlib[2], 0);
+        assert_eq!(zlib[2 + 5 + 0xffff], 1);
+    }
+
+    #[test]
+    fn roundtrips_lzw() {
+        let mut data: Vec<u8> = (0..20_000_u32).map(|i| ((i * i) % 7) as u8).collect();
+        data.extend(std::iter::repeat_n(3, 5000));
+        assert_eq!(lzw_decode(&gif::lzw(&data, 3), 3), data);
+
+        let noise: Vec<u8> = (0..50_000_u32)
+            .map(|i| (i.wrapping_mul(2_654_435_761) >> 24) as u8)
+            .collect();
+        assert_eq!(lzw_decode(&gif::lzw(&noise, 8), 8), noise);
+
+        assert_eq!(lzw_decode(&gif::lzw(&[1], 2), 2), vec![1]);
+    }
+
+    #[test]
+    fn encodes_animations() {
+        let mut animation = Animation::new(Duration::from_millis(100));
+        for i in 0..3 {
+            let grid = Array2::from_shape_fn((4, 4), |(y, x)| x == i || y == i);
+            animation
+                .push(&Image::from_bool_grid(&grid, Rgb::BLACK, Rgb::WHITE, 1))
+                .unwrap();
+        }
+        let gif = animation.to_gif().unwrap();
+        assert_eq!(&gif[..6], b"GIF89a");
+        assert_eq!(&gif[6..10], &[4, 0, 4, 0]);
+        assert_eq!(gif.windows(11).filter(|w| *w == b"NETSCAPE2.0").count(), 1);
+        assert_eq!(
+            gif.windows(3).filter(|w| *w == [0x21, 0xf9, 0x04]).count(),
+            3
+        );
+        assert_eq!(gif.last(), Some(&0x3b));
+
+        let wrong_size = Image::new(2, 2, Rgb::BLACK);
+        assert!(matches!(
+            animation.push(&wrong_size),
+            Err(ImageError::SizeMismatch { .. })
+        ));
+        assert!(matches!(
+            Animation::new(Duration::ZERO).to_gif(),
+            Err(ImageError::Empty)
+        ));
+    }
+}
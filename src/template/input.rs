@@ -0,0 +1,338 @@
+/// Validation and normalization of puzzle files before they are handed to a solution.
+use std::{
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+};
+
+use crate::template::{config, crypto, Day, ANSI_ITALIC, ANSI_RESET};
+
+/// Markers that indicate the file holds an error page instead of puzzle data.
+const HTML_MARKERS: [&str; 3] = ["<!doctype html", "<html", "<head>"];
+
+/// Markers that indicate the file holds the response for an unauthenticated request.
+const LOGIN_MARKERS: [&str; 2] = ["please log in", "puzzle inputs differ by user"];
+
+#[derive(Debug)]
+pub enum InputError {
+    NotFound {
+        path: PathBuf,
+        folder: String,
+        day: Day,
+    },
+    Empty {
+        path: PathBuf,
+        folder: String,
+        day: Day,
+    },
+    /// The file is encrypted and could not be decrypted.
+    Decrypt {
+        path: PathBuf,
+        error: String,
+    },
+    IO(io::Error),
+}
+
+impl Display for InputError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputError::NotFound { path, folder, day } => {
+                write!(f, "could not find file \"{}\"", path.display())?;
+                write_hint(f, folder, *day)
+            }
+            InputError::Empty { path, folder, day } => {
+                write!(f, "file \"{}\" is empty", path.display())?;
+                write_hint(f, folder, *day)
+            }
+            InputError::Decrypt { path, error } => {
+                write!(f, "file \"{}\" is encrypted - {error}", path.display())
+            }
+            InputError::IO(e) => write!(f, "could not open input file: {e}"),
+        }
+    }
+}
+
+fn write_hint(f: &mut std::fmt::Formatter<'_>, folder: &str, day: Day) -> std::fmt::Result {
+    match folder {
+        "inputs" => write!(f, " - did you forget `cargo download {day}`?"),
+        "examples" => write!(
+            f,
+            " - paste the example from the puzzle description into it."
+        ),
+        _ => Ok(()),
+    }
+}
+
+/// Returned by [`validate`] for files without any content.
+#[derive(Debug)]
+pub struct EmptyInput;
+
+/// Non-fatal issues that hint at a broken download.
+#[derive(Debug, PartialEq, Eq)]
+pub enum InputWarning {
+    LooksLikeHtml,
+    LooksLikeLoginPrompt,
+}
+
+impl Display for InputWarning {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputWarning::LooksLikeHtml => {
+                write!(f, "file looks like an HTML page instead of puzzle data.")
+            }
+            InputWarning::LooksLikeLoginPrompt => write!(
+                f,
+                "file looks like a \"Please log in\" response. Your session cookie might have expired."
+            ),
+        }
+    }
+}
+
+/// Converts `\r\n` and `\r` line endings to `\n` and collapses trailing newlines into a single one.
+#[must_use]
+pub fn normalize(raw: &str) -> String {
+    let mut normalized = raw.replace("\r\n", "\n").replace('\r', "\n");
+    let trimmed_len = normalized.trim_end_matches('\n').len();
+    normalized.truncate(trimmed_len);
+    normalized.push('\n');
+    normalized
+}
+
+/// Checks the contents of a file for common download problems.
+/// Whitespace-only contents are treated as empty.
+pub fn validate(contents: &str) -> Result<Vec<InputWarning>, EmptyInput> {
+    if contents.trim().is_empty() {
+        return Err(EmptyInput);
+    }
+
+    let head = contents
+        .chars()
+        .take(512)
+        .collect::<String>()
+        .to_lowercase();
+
+    let mut warnings = vec![];
+
+    if HTML_MARKERS
+        .iter()
+        .any(|m| head.trim_start().starts_with(m))
+    {
+        warnings.push(InputWarning::LooksLikeHtml);
+    }
+
+    if LOGIN_MARKERS.iter().any(|m| head.contains(m)) {
+        warnings.push(InputWarning::LooksLikeLoginPrompt);
+    }
+
+    Ok(warnings)
+}
+
+/// Reads, validates and normalizes a file from the configured data folder, e.g. `data/inputs/01.txt`.
+/// Encrypted files are decrypted in memory with the input key.
+/// Warnings are printed to stderr so they do not interfere with the runner output.
+pub fn load(folder: &str, file_name: &str, day: Day) -> Result<String, InputError> {
+    load_path(&config::get().data_dir(folder).join(file_name), folder, day)
+}
+
+/// Like [`load`], for any path. `folder` selects the hint shown for missing or empty files.
+pub fn load_path(path: &Path, folder: &str, day: Day) -> Result<String, InputError> {
+    let path = path.to_path_buf();
+
+    let raw = fs::read(&path).map_err(|e| match e.kind() {
+        io::ErrorKind::NotFound => InputError::NotFound {
+            path: path.clone(),
+            folder: folder.into(),
+            day,
+        },
+        _ => InputError::IO(e),
+    })?;
+
+    // encrypted inputs are only decrypted in memory.
+    let raw = crypto::plain(raw).map_err(|e| InputError::Decrypt {
+        path: path.clone(),
+        error: e.to_string(),
+    })?;
+
+    let raw = String::from_utf8(raw)
+        .map_err(|e| InputError::IO(io::Error::new(io::ErrorKind::InvalidData, e)))?;
+
+    let warnings = validate(&raw).map_err(|EmptyInput| InputError::Empty {
+        path: path.clone(),
+        folder: folder.into(),
+        day,
+    })?;
+
+    for warning in warnings {
+        eprintln!("Warning: \"{}\": {warning}", path.display());
+    }
+
+    Ok(normalize(&raw))
+}
+
+/// The real inputs of the parts of a day, used by the [`day_module!`](crate::day_module) macro.
+/// A part reads `XX-<part>.txt` if it exists and the shared `XX.txt` otherwise.
+pub struct PartInputs {
+    /// File names and contents, each file is read once.
+    files: Vec<(String, String)>,
+    /// Index into `files` for each part.
+    parts: Vec<(u8, usize)>,
+}
+
+impl PartInputs {
+    /// Reads the inputs of the given parts. Panics with a hint if one cannot be read, like [`read_file`](crate::template::read_file).
+    pub fn load(day: Day, parts: &[u8]) -> Self {
+        let dir = config::get().data_dir("inputs");
+        let mut inputs = PartInputs {
+            files: vec![],
+            parts: vec![],
+        };
+
+        for &part in parts {
+            let file_name = file_name_for_part(day, part, |name| dir.join(name).exists());
+            let index = match inputs.files.iter().position(|(name, _)| *name == file_name) {
+                Some(index) => index,
+                None => {
+                    let contents =
+                        load("inputs", &file_name, day).unwrap_or_else(|e| panic!("{e}"));
+                    inputs.files.push((file_name, contents));
+                    inputs.files.len() - 1
+                }
+            };
+            inputs.parts.push((part, index));
+        }
+
+        inputs
+    }
+
+    /// The input of all parts, if they read the same file. Prints the file.
+    pub fn shared(&self) -> Option<&str> {
+        match self.files.as_slice() {
+            [(file_name, contents)] => {
+                print_file(file_name);
+                Some(contents)
+            }
+            _ => None,
+        }
+    }
+
+    /// The input of a part. Prints the file of this part.
+    pub fn for_part(&self, part: u8) -> &str {
+        let (file_name, contents) = &self.files[self.index(part)];
+        print_file(file_name);
+        contents
+    }
+
+    /// The name of the file a part reads, e.g. `01-2.txt`.
+    pub fn file_name(&self, part: u8) -> &str {
+        &self.files[self.index(part)].0
+    }
+
+    fn index(&self, part: u8) -> usize {
+        self.parts
+            .iter()
+            .find(|(p, _)| *p == part)
+            .map_or_else(|| panic!("part {part} was not loaded."), |(_, index)| *index)
+    }
+}
+
+fn print_file(file_name: &str) {
+    let path = config::get().data_dir("inputs").join(file_name);
+    println!("{ANSI_ITALIC}Input: {}{ANSI_RESET}", path.display());
+}
+
+fn file_name_for_part(day: Day, part: u8, exists: impl Fn(&str) -> bool) -> String {
+    let part_file = format!("{day}-{part}.txt");
+    if exists(&part_file) {
+        part_file
+    } else {
+        format!("{day}.txt")
+    }
+}
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{file_name_for_part, normalize, validate, InputWarning, PartInputs};
+    use crate::day;
+
+    fn part_inputs(files: &[(&str, &str)], parts: &[(u8, usize)]) -> PartInputs {
+        PartInputs {
+            files: files
+                .iter()
+                .map(|(name, contents)| (name.to_string(), contents.to_string()))
+                .collect(),
+            parts: parts.to_vec(),
+        }
+    }
+
+    #[test]
+    fn shares_a_single_input_file() {
+        let inputs = part_inputs(&[("01.txt", "1 2\n")], &[(1, 0), (2, 0)]);
+        assert_eq!(inputs.shared(), Some("1 2\n"));
+        assert_eq!(inputs.for_part(1), "1 2\n");
+        assert_eq!(inputs.for_part(2), "1 2\n");
+        assert_eq!(inputs.file_name(2), "01.txt");
+    }
+
+    #[test]
+    fn reads_an_input_file_per_part() {
+        let inputs = part_inputs(
+            &[("01.txt", "1 2\n"), ("01-2.txt", "3 4\n")],
+            &[(1, 0), (2, 1)],
+        );
+        assert_eq!(inputs.shared(), None);
+        assert_eq!(inputs.for_part(1), "1 2\n");
+        assert_eq!(inputs.for_part(2), "3 4\n");
+        assert_eq!(inputs.file_name(1), "01.txt");
+        assert_eq!(inputs.file_name(2), "01-2.txt");
+    }
+
+    #[test]
+    fn normalizes_line_endings() {
+        assert_eq!(normalize("1 2\r\n3 4\r\n"), "1 2\n3 4\n");
+        assert_eq!(normalize("1 2\r3 4"), "1 2\n3 4\n");
+    }
+
+    #[test]
+    fn normalizes_trailing_newlines() {
+        assert_eq!(normalize("abc"), "abc\n");
+        assert_eq!(normalize("abc\n\n\n"), "abc\n");
+        assert_eq!(normalize("abc\r\n\r\n"), "abc\n");
+    }
+
+    #[test]
+    fn keeps_inner_blank_lines() {
+        assert_eq!(normalize("a|b\n\n1,2\n"), "a|b\n\n1,2\n");
+    }
+
+    #[test]
+    fn rejects_empty_input() {
+        assert!(validate("").is_err());
+        assert!(validate(" \n\r\n").is_err());
+    }
+
+    #[test]
+    fn accepts_puzzle_data() {
+        assert_eq!(validate("3   4\n4   3\n").unwrap(), vec![]);
+    }
+
+    #[test]
+    fn warns_for_html() {
+        let warnings = validate("<!DOCTYPE html>\n<html lang=\"en-us\">").unwrap();
+        assert_eq!(warnings, vec![InputWarning::LooksLikeHtml]);
+    }
+
+    #[test]
+    fn warns_for_login_prompt() {
+        let warnings =
+            validate("Puzzle inputs differ by user.  Please log in to get your puzzle input.\n")
+                .unwrap();
+        assert_eq!(warnings, vec![InputWarning::LooksLikeLoginPrompt]);
+    }
+
+    #[test]
+    fn prefers_part_files() {
+        let exists = |name: &str| name == "01-2.txt";
+        assert_eq!(file_name_for_part(day!(1), 1, exists), "01.txt");
+        assert_eq!(file_name_for_part(day!(1), 2, exists), "01-2.txt");
+    }
+}
@@ -0,0 +1,249 @@
+/// Describes the machine and toolchain that benchmarks were recorded with.
+use std::{collections::HashMap, fmt::Display, fs, process::Command};
+
+use tinyjson::JsonValue;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Machine {
+    pub cpu: String,
+    pub cores: usize,
+    pub rustc: String,
+    pub profile: String,
+    pub features: Vec<String>,
+    pub commit: Option<String>,
+}
+
+impl Machine {
+    /// Collects information about the current machine for a solution build with `profile` and `features`.
+    pub fn detect(profile: &str, features: &[String]) -> Self {
+        Self {
+            cpu: cpu_model().unwrap_or_else(|| "unknown CPU".into()),
+            cores: std::thread::available_parallelism().map_or(1, std::num::NonZero::get),
+            rustc: rustc_version().unwrap_or_else(|| "unknown rustc".into()),
+            profile: profile.into(),
+            features: features.to_vec(),
+            commit: git_commit(),
+        }
+    }
+
+    /// Identifies timings that are comparable with each other.
+    /// Toolchain and commit are not part of the key, newer runs replace older ones.
+    pub fn key(&self) -> String {
+        let mut key = format!("{} ({} cores), {}", self.cpu, self.cores, self.profile);
+        if !self.features.is_empty() {
+            key.push_str(&format!(" +{}", self.features.join(",")));
+        }
+        key
+    }
+}
+
+#[cfg(feature = "test_lib")]
+impl Machine {
+    /// A fixed machine for tests that store or print timings.
+    pub fn mock(cpu: &str) -> Self {
+        Self {
+            cpu: cpu.into(),
+            cores: 8,
+            rustc: "rustc 1.83.0".into(),
+            profile: "release".into(),
+            features: vec![],
+            commit: Some("1a2b3c4".into()),
+        }
+    }
+}
+
+impl Display for Machine {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} ({} cores), {}, `{}` profile",
+            self.cpu, self.cores, self.rustc, self.profile
+        )?;
+
+        if !self.features.is_empty() {
+            write!(f, ", features: {}", self.features.join(", "))?;
+        }
+
+        if let Some(commit) = &self.commit {
+            write!(f, ", commit `{commit}`")?;
+        }
+
+        Ok(())
+    }
+}
+
+fn cpu_model() -> Option<String> {
+    if let Ok(cpuinfo) = fs::read_to_string("/proc/cpuinfo") {
+        return parse_cpuinfo(&cpuinfo);
+    }
+
+    if let Some(brand) = command_output("sysctl", &["-n", "machdep.cpu.brand_string"]) {
+        return Some(brand);
+    }
+
+    std::env::var("PROCESSOR_IDENTIFIER").ok()
+}
+
+fn parse_cpuinfo(cpuinfo: &str) -> Option<String> {
+    ["model name", "Hardware", "cpu model", "cpu"]
+        .iter()
+        .find_map(|field| {
+            cpuinfo.lines().find_map(|line| {
+                let (key, value) = line.split_once(':')?;
+                (key.trim() == *field && !value.trim().is_empty()).then(|| value.trim().to_string())
+            })
+        })
+        .map(|model| model.split_whitespace().collect::<Vec<_>>().join(" "))
+}
+
+fn rustc_version() -> Option<String> {
+    let rustc = std::env::var("RUSTC").unwrap_or_else(|_| "rustc".into());
+    command_output(&rustc, &["--version"])
+}
+
+fn git_commit() -> Option<String> {
+    let commit = command_output("git", &["rev-parse", "--short", "HEAD"])?;
+    let is_dirty = command_output("git", &["status", "--porcelain", "--untracked-files=no"])
+        .is_some_and(|status| !status.is_empty());
+
+    Some(if is_dirty {
+        format!("{commit}-dirty")
+    } else {
+        commit
+    })
+}
+
+fn command_output(cmd: &str, args: &[&str]) -> Option<String> {
+    let output = Command::new(cmd).args(args).output().ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Machine> for JsonValue {
+    fn from(value: &Machine) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert("cpu".into(), JsonValue::String(value.cpu.clone()));
+        #[allow(clippy::cast_precision_loss)]
+        map.insert("cores".into(), JsonValue::Number(value.cores as f64));
+        map.insert("rustc".into(), JsonValue::String(value.rustc.clone()));
+        map.insert("profile".into(), JsonValue::String(value.profile.clone()));
+        map.insert(
+            "features".into(),
+            JsonValue::Array(
+                value
+                    .features
+                    .iter()
+                    .cloned()
+                    .map(JsonValue::String)
+                    .collect(),
+            ),
+        );
+        map.insert(
+            "commit".into(),
+            value
+                .commit
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Machine {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected machine to be a JSON object.")?;
+
+        let get_string = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<String>())
+                .cloned()
+                .ok_or(format!("Expected machine.{key} to be a string."))
+        };
+
+        #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+        let cores = json
+            .get("cores")
+            .and_then(|v| v.get::<f64>())
+            .map(|cores| *cores as usize)
+            .ok_or("Expected machine.cores to be a number.")?;
+
+        let features = json
+            .get("features")
+            .and_then(|v| v.get::<Vec<JsonValue>>())
+            .ok_or("Expected machine.features to be an array.")?
+            .iter()
+            .map(|v| v.get::<String>().cloned())
+            .collect::<Option<Vec<_>>>()
+            .ok_or("Expected machine.features to contain strings.")?;
+
+        let commit = json
+            .get("commit")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected machine.commit to be null or string.")?;
+
+        Ok(Machine {
+            cpu: get_string("cpu")?,
+            cores,
+            rustc: get_string("rustc")?,
+            profile: get_string("profile")?,
+            features,
+            commit: commit.cloned(),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::{parse_cpuinfo, Machine};
+
+    #[test]
+    fn parses_cpuinfo() {
+        let cpuinfo = "processor\t: 0\nvendor_id\t: AuthenticAMD\nmodel name\t: AMD Ryzen 7  5800X 8-Core Processor\n";
+        assert_eq!(
+            parse_cpuinfo(cpuinfo),
+            Some("AMD Ryzen 7 5800X 8-Core Processor".into())
+        );
+        assert_eq!(parse_cpuinfo("processor\t: 0\n"), None);
+    }
+
+    #[test]
+    fn keys_by_hardware_and_build() {
+        let machine = Machine::mock("AMD Ryzen 7 5800X 8-Core Processor");
+        let newer_toolchain = Machine {
+            rustc: "rustc 1.84.0".into(),
+            commit: None,
+            ..machine.clone()
+        };
+        let debug_build = Machine {
+            profile: "dev".into(),
+            ..machine.clone()
+        };
+
+        assert_eq!(machine.key(), newer_toolchain.key());
+        assert_ne!(machine.key(), debug_build.key());
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let machine = Machine {
+            features: vec!["dhat-heap".into()],
+            ..Machine::mock("AMD Ryzen 7 5800X 8-Core Processor")
+        };
+        let json = JsonValue::from(&machine);
+        assert_eq!(Machine::try_from(&json).unwrap(), machine);
+    }
+}
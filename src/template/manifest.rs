@@ -0,0 +1,291 @@
+/// Records checksums of downloaded inputs and puzzles to detect files that were edited or overwritten later.
+use std::{collections::BTreeMap, collections::HashMap, fmt::Display, fs, io, path::PathBuf};
+
+use tinyjson::JsonValue;
+
+use crate::template::{config, crypto, sha256, Day};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Kind {
+    Input,
+    Puzzle,
+}
+
+impl Kind {
+    fn key(self) -> &'static str {
+        match self {
+            Kind::Input => "inputs",
+            Kind::Puzzle => "puzzles",
+        }
+    }
+
+    pub fn path(self, day: Day) -> PathBuf {
+        match self {
+            Kind::Input => config::get().input_path(day),
+            Kind::Puzzle => config::get().puzzle_path(day),
+        }
+    }
+}
+
+impl Display for Kind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Kind::Input => write!(f, "input"),
+            Kind::Puzzle => write!(f, "puzzle"),
+        }
+    }
+}
+
+/// Fingerprint of a file as it was downloaded.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Entry {
+    pub sha256: String,
+    pub size: u64,
+}
+
+impl Entry {
+    pub fn of(contents: &[u8]) -> Self {
+        Self {
+            sha256: sha256::hex(&sha256::digest(contents)),
+            size: contents.len() as u64,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Status {
+    Ok,
+    /// The file exists but differs from the downloaded one. Holds the current size.
+    Modified(u64),
+    Missing,
+    /// The file exists but could not be read or decrypted. Holds the error.
+    Unreadable(String),
+    /// The file was not downloaded via the template.
+    Untracked,
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Manifest {
+    entries: BTreeMap<(Kind, Day), Entry>,
+}
+
+impl Manifest {
+    /// Rehydrate the manifest from its JSON file. If not present, returns an empty manifest.
+    pub fn read_from_file() -> Result<Self, String> {
+        match fs::read_to_string(&config::get().manifest_path) {
+            Ok(s) => Manifest::try_from(s),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Manifest::default()),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    /// Dehydrate the manifest to its JSON file.
+    pub fn store_file(&self) -> Result<(), io::Error> {
+        let path = &config::get().manifest_path;
+        if let Some(parent) = path.parent().filter(|p| !p.as_os_str().is_empty()) {
+            fs::create_dir_all(parent)?;
+        }
+        let json = JsonValue::from(self);
+        let mut file = fs::File::create(path)?;
+        json.format_to(&mut file)
+    }
+
+    /// Records the current contents of the file as the downloaded version.
+    /// Encrypted files are fingerprinted by their decrypted contents, so changing the key keeps them valid.
+    pub fn record(&mut self, kind: Kind, day: Day) -> Result<(), String> {
+        let contents = crypto::read_plain(&kind.path(day)).map_err(|e| e.to_string())?;
+        self.entries.insert((kind, day), Entry::of(&contents));
+        Ok(())
+    }
+
+    pub fn get(&self, kind: Kind, day: Day) -> Option<&Entry> {
+        self.entries.get(&(kind, day))
+    }
+
+    /// Compares the file on disk with the recorded fingerprint.
+    pub fn check(&self, kind: Kind, day: Day) -> Status {
+        let Some(entry) = self.get(kind, day) else {
+            return Status::Untracked;
+        };
+
+        match crypto::read_plain(&kind.path(day)) {
+            Ok(contents) => {
+                let current = Entry::of(&contents);
+                if current == *entry {
+                    Status::Ok
+                } else {
+                    Status::Modified(current.size)
+                }
+            }
+            Err(crypto::CryptoError::IO(e)) if e.kind() == io::ErrorKind::NotFound => {
+                Status::Missing
+            }
+            Err(e) => Status::Unreadable(e.to_string()),
+        }
+    }
+
+    /// All recorded files, optionally limited to one day.
+    pub fn tracked(&self, day: Option<Day>) -> Vec<(Kind, Day)> {
+        self.entries
+            .keys()
+            .filter(|(_, d)| day.is_none_or(|day| day == *d))
+            .copied()
+            .collect()
+    }
+}
+
+/// Prints a warning to stderr if the input of `day` no longer matches the downloaded one.
+pub fn warn_if_modified(day: Day) {
+    let Ok(manifest) = Manifest::read_from_file() else {
+        return;
+    };
+
+    let path = Kind::Input.path(day);
+    let message = match manifest.check(Kind::Input, day) {
+        // unreadable inputs fail with a more helpful error when the solution reads them.
+        Status::Ok | Status::Untracked | Status::Unreadable(_) => return,
+        Status::Missing => format!("{} was downloaded but is missing.", path.display()),
+        Status::Modified(size) => {
+            let recorded = manifest.get(Kind::Input, day).map_or(0, |e| e.size);
+            format!(
+                "{} differs from the downloaded input ({size} bytes, downloaded {recorded} bytes).",
+                path.display()
+            )
+        }
+    };
+
+    eprintln!("⚠️  {message} Run `cargo inputs restore {day}` to download it again.");
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Manifest> for JsonValue {
+    fn from(value: &Manifest) -> Self {
+        let mut kinds: HashMap<String, JsonValue> = HashMap::new();
+
+        for ((kind, day), entry) in &value.entries {
+            let mut map: HashMap<String, JsonValue> = HashMap::new();
+            map.insert("sha256".into(), JsonValue::String(entry.sha256.clone()));
+            #[allow(clippy::cast_precision_loss)]
+            map.insert("size".into(), JsonValue::Number(entry.size as f64));
+
+            let days = kinds
+                .entry(kind.key().into())
+                .or_insert_with(|| JsonValue::Object(HashMap::new()));
+            if let JsonValue::Object(days) = days {
+                days.insert(day.to_string(), JsonValue::Object(map));
+            }
+        }
+
+        JsonValue::Object(kinds)
+    }
+}
+
+impl TryFrom<String> for Manifest {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = value.parse::<JsonValue>().map_err(|x| x.to_string())?;
+        let kinds = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected manifest to be a JSON object.")?;
+
+        let mut entries = BTreeMap::new();
+
+        for kind in [Kind::Input, Kind::Puzzle] {
+            let Some(days) = kinds.get(kind.key()) else {
+                continue;
+            };
+            let days = days
+                .get::<HashMap<String, JsonValue>>()
+                .ok_or(format!("Expected manifest.{} to be an object.", kind.key()))?;
+
+            for (day, entry) in days {
+                let day = day
+                    .parse::<Day>()
+                    .map_err(|_| format!("Expected \"{day}\" to be a day."))?;
+                let sha256 = entry
+                    .get::<HashMap<String, JsonValue>>()
+                    .and_then(|e| e.get("sha256"))
+                    .and_then(|v| v.get::<String>())
+                    .ok_or("Expected manifest entry to have a sha256 string.")?;
+                #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+                let size = entry
+                    .get::<HashMap<String, JsonValue>>()
+                    .and_then(|e| e.get("size"))
+                    .and_then(|v| v.get::<f64>())
+                    .map(|size| *size as u64)
+                    .ok_or("Expected manifest entry to have a size number.")?;
+
+                entries.insert(
+                    (kind, day),
+                    Entry {
+                        sha256: sha256.clone(),
+                        size,
+                    },
+                );
+            }
+        }
+
+        Ok(Manifest { entries })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use tinyjson::JsonValue;
+
+    use super::{Entry, Kind, Manifest};
+    use crate::day;
+
+    #[test]
+    fn fingerprints_contents() {
+        let entry = Entry::of(b"abc");
+        assert_eq!(entry.size, 3);
+        assert_eq!(
+            entry.sha256,
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+        assert_ne!(Entry::of(b"abc\n"), entry);
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let mut manifest = Manifest::default();
+        manifest
+            .entries
+            .insert((Kind::Input, day!(6)), Entry::of(b"..#.\n"));
+        manifest
+            .entries
+            .insert((Kind::Puzzle, day!(6)), Entry::of(b"# Day 6"));
+
+        let json = JsonValue::from(&manifest).stringify().unwrap();
+        assert!(json.contains("\"inputs\""));
+        assert!(json.contains("\"06\""));
+        assert_eq!(Manifest::try_from(json).unwrap(), manifest);
+    }
+
+    #[test]
+    fn lists_tracked_files() {
+        let mut manifest = Manifest::default();
+        manifest
+            .entries
+            .insert((Kind::Input, day!(1)), Entry::of(b"1"));
+        manifest
+            .entries
+            .insert((Kind::Input, day!(2)), Entry::of(b"2"));
+        assert_eq!(manifest.tracked(None).len(), 2);
+        assert_eq!(
+            manifest.tracked(Some(day!(2))),
+            vec![(Kind::Input, day!(2))]
+        );
+    }
+
+    #[test]
+    fn errors_for_invalid_days() {
+        let json = r#"{ "inputs": { "26": { "sha256": "", "size": 0 } } }"#.to_string();
+        assert!(Manifest::try_from(json).is_err());
+    }
+}
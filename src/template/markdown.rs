@@ -0,0 +1,468 @@
+/// Renders the puzzle descriptions stored by aoc-cli (`data/puzzles/XX.md`) for the terminal.
+///
+/// Supports the markdown subset that aoc-cli produces: ATX and setext headings, paragraphs, lists,
+/// fenced and indented code blocks, emphasis, inline code and links.
+use crate::template::{ANSI_BOLD, ANSI_RESET};
+
+const ANSI_DIM: &str = "\x1b[2m";
+const ANSI_UNDERLINE: &str = "\x1b[4m";
+const ANSI_GREEN: &str = "\x1b[32m";
+const ANSI_YELLOW: &str = "\x1b[33m";
+const ANSI_CYAN: &str = "\x1b[36m";
+
+const CODE_INDENT: &str = "    ";
+const ANSWER_PREFIX: &str = "Your puzzle answer was";
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+struct Style {
+    strong: bool,
+    emphasis: bool,
+    code: bool,
+    link: bool,
+    answer: bool,
+}
+
+impl Style {
+    fn ansi(self, colored: bool) -> String {
+        if !colored {
+            return String::new();
+        }
+
+        let mut codes = String::new();
+        if self.answer && self.code {
+            codes.push_str(ANSI_BOLD);
+            codes.push_str(ANSI_GREEN);
+        } else if self.code {
+            codes.push_str(ANSI_CYAN);
+        }
+        if self.strong || self.emphasis {
+            codes.push_str(ANSI_BOLD);
+        }
+        if self.emphasis {
+            codes.push_str(ANSI_YELLOW);
+        }
+        if self.link {
+            codes.push_str(ANSI_UNDERLINE);
+        }
+        codes
+    }
+}
+
+/// A word that can not be broken up when wrapping, made of differently styled pieces.
+type Word = Vec<(String, Style)>;
+
+/// Renders `markdown` wrapped to `width` columns. Without `colored`, only the layout is applied.
+#[must_use]
+pub fn render(markdown: &str, width: usize, colored: bool) -> String {
+    let width = width.max(20);
+    let mut out: Vec<String> = vec![];
+    let mut paragraph: Vec<&str> = vec![];
+    let mut in_fence = false;
+
+    let lines: Vec<&str> = markdown.lines().collect();
+    let mut idx = 0;
+
+    while idx < lines.len() {
+        let line = lines[idx];
+        let trimmed = line.trim();
+        idx += 1;
+
+        if trimmed.starts_with("```") {
+            flush_paragraph(&mut out, &mut paragraph, width, colored);
+            in_fence = !in_fence;
+            continue;
+        }
+
+        if in_fence {
+            out.push(code_line(line, colored));
+            continue;
+        }
+
+        if trimmed.is_empty() {
+            flush_paragraph(&mut out, &mut paragraph, width, colored);
+            if out.last().is_some_and(|l| !l.is_empty()) {
+                out.push(String::new());
+            }
+            continue;
+        }
+
+        // setext heading: a paragraph line underlined with `---` or `===`.
+        if is_setext_underline(trimmed) {
+            if let Some(heading) = paragraph.pop() {
+                flush_paragraph(&mut out, &mut paragraph, width, colored);
+                push_heading(&mut out, heading, width, colored);
+            } else {
+                push_block(&mut out, vec![rule(width, colored)]);
+            }
+            continue;
+        }
+
+        if paragraph.is_empty() && (line.starts_with(CODE_INDENT) || line.starts_with('\t')) {
+            out.push(code_line(line.trim_start_matches('\t'), colored));
+            continue;
+        }
+
+        if let Some(heading) = atx_heading(trimmed) {
+            flush_paragraph(&mut out, &mut paragraph, width, colored);
+            push_heading(&mut out, heading, width, colored);
+            continue;
+        }
+
+        if is_reference_definition(trimmed) {
+            continue;
+        }
+
+        if let Some((marker, item)) = list_item(trimmed) {
+            flush_paragraph(&mut out, &mut paragraph, width, colored);
+            let mut text = item.to_string();
+            // continuation lines of the list item.
+            while idx < lines.len() {
+                let next = lines[idx].trim();
+                if next.is_empty() || list_item(next).is_some() || lines[idx].starts_with("```") {
+                    break;
+                }
+                text.push(' ');
+                text.push_str(next);
+                idx += 1;
+            }
+            let indent = " ".repeat(marker.chars().count() + 1);
+            let words = parse_inline(&text, false);
+            let mut wrapped = wrap(&words, width - indent.len(), colored);
+            for (line_idx, line) in wrapped.iter_mut().enumerate() {
+                *line = if line_idx == 0 {
+                    format!("{marker} {line}")
+                } else {
+                    format!("{indent}{line}")
+                };
+            }
+            out.extend(wrapped);
+            continue;
+        }
+
+        paragraph.push(trimmed);
+    }
+
+    flush_paragraph(&mut out, &mut paragraph, width, colored);
+
+    while out.last().is_some_and(String::is_empty) {
+        out.pop();
+    }
+
+    let mut rendered = out.join("\n");
+    rendered.push('\n');
+    rendered
+}
+
+fn push_block(out: &mut Vec<String>, block: Vec<String>) {
+    if out.last().is_some_and(|l| !l.is_empty()) {
+        out.push(String::new());
+    }
+    out.extend(block);
+    out.push(String::new());
+}
+
+fn flush_paragraph(out: &mut Vec<String>, paragraph: &mut Vec<&str>, width: usize, colored: bool) {
+    if paragraph.is_empty() {
+        return;
+    }
+    let text = paragraph.join(" ");
+    paragraph.clear();
+    let words = parse_inline(&text, text.starts_with(ANSWER_PREFIX));
+    push_block(out, wrap(&words, width, colored));
+}
+
+fn push_heading(out: &mut Vec<String>, heading: &str, width: usize, colored: bool) {
+    let words = parse_inline(heading, false);
+    let lines = wrap(&words, width, colored)
+        .into_iter()
+        .map(|line| {
+            if colored {
+                format!("{ANSI_BOLD}{ANSI_GREEN}{line}{ANSI_RESET}")
+            } else {
+                line
+            }
+        })
+        .collect();
+    push_block(out, lines);
+}
+
+fn rule(width: usize, colored: bool) -> String {
+    let rule = "─".repeat(width.min(40));
+    if colored {
+        format!("{ANSI_DIM}{rule}{ANSI_RESET}")
+    } else {
+        rule
+    }
+}
+
+fn code_line(line: &str, colored: bool) -> String {
+    let line = line.strip_prefix(CODE_INDENT).unwrap_or(line);
+    if colored {
+        format!("{CODE_INDENT}{ANSI_CYAN}{line}{ANSI_RESET}")
+    } else {
+        format!("{CODE_INDENT}{line}")
+    }
+}
+
+fn is_setext_underline(line: &str) -> bool {
+    line.len() >= 3 && (line.chars().all(|c| c == '-') || line.chars().all(|c| c == '='))
+}
+
+fn atx_heading(line: &str) -> Option<&str> {
+    let hashes = line.chars().take_while(|c| *c == '#').count();
+    if !(1..=6).contains(&hashes) {
+        return None;
+    }
+    let rest = &line[hashes..];
+    rest.starts_with(' ')
+        .then(|| rest.trim().trim_end_matches('#').trim_end())
+}
+
+fn is_reference_definition(line: &str) -> bool {
+    line.starts_with('[')
+        && line
+            .split_once("]:")
+            .is_some_and(|(label, _)| !label.contains(']'))
+}
+
+fn list_item(line: &str) -> Option<(String, &str)> {
+    for bullet in ["* ", "- ", "+ "] {
+        if let Some(item) = line.strip_prefix(bullet) {
+            return Some(("•".into(), item.trim_start()));
+        }
+    }
+
+    let digits = line.chars().take_while(char::is_ascii_digit).count();
+    if digits > 0 {
+        if let Some(item) = line[digits..].strip_prefix(". ") {
+            return Some((line[..=digits].to_string(), item.trim_start()));
+        }
+    }
+
+    None
+}
+
+/// Splits inline markdown into styled words.
+fn parse_inline(text: &str, is_answer: bool) -> Vec<Word> {
+    let mut words: Vec<Word> = vec![];
+    let mut word: Word = vec![];
+    let mut piece = String::new();
+    let mut style = Style {
+        answer: is_answer,
+        ..Style::default()
+    };
+
+    let chars: Vec<char> = text.chars().collect();
+    let mut idx = 0;
+
+    let flush_piece = |word: &mut Word, piece: &mut String, style: Style| {
+        if !piece.is_empty() {
+            word.push((std::mem::take(piece), style));
+        }
+    };
+
+    while idx < chars.len() {
+        let c = chars[idx];
+        let next = chars.get(idx + 1).copied();
+
+        match c {
+            '\\' if !style.code && next.is_some_and(|n| n.is_ascii_punctuation()) => {
+                piece.push(next.unwrap_or_default());
+                idx += 2;
+                continue;
+            }
+            '`' => {
+                flush_piece(&mut word, &mut piece, style);
+                style.code = !style.code;
+            }
+            _ if style.code && !c.is_whitespace() => piece.push(c),
+            '*' | '_' if next == Some(c) => {
+                flush_piece(&mut word, &mut piece, style);
+                style.strong = !style.strong;
+                idx += 2;
+                continue;
+            }
+            '*' => {
+                flush_piece(&mut word, &mut piece, style);
+                style.emphasis = !style.emphasis;
+            }
+            '_' if style.emphasis || is_word_boundary(&chars, idx) => {
+                flush_piece(&mut word, &mut piece, style);
+                style.emphasis = !style.emphasis;
+            }
+            '[' if !style.link && find_link_end(&chars, idx).is_some() => {
+                flush_piece(&mut word, &mut piece, style);
+                style.link = true;
+            }
+            ']' if style.link => {
+                flush_piece(&mut word, &mut piece, style);
+                style.link = false;
+                // skip the link target, i.e. `(url)` or `[ref]`.
+                if let Some(close) = match next {
+                    Some('(') => chars[idx + 1..].iter().position(|c| *c == ')'),
+                    Some('[') => chars[idx + 1..].iter().position(|c| *c == ']'),
+                    _ => None,
+                } {
+                    idx += close + 2;
+                    continue;
+                }
+            }
+            c if c.is_whitespace() => {
+                flush_piece(&mut word, &mut piece, style);
+                if !word.is_empty() {
+                    words.push(std::mem::take(&mut word));
+                }
+            }
+            c => piece.push(c),
+        }
+
+        idx += 1;
+    }
+
+    flush_piece(&mut word, &mut piece, style);
+    if !word.is_empty() {
+        words.push(word);
+    }
+
+    words
+}
+
+fn is_word_boundary(chars: &[char], idx: usize) -> bool {
+    let before = idx.checked_sub(1).and_then(|i| chars.get(i));
+    before.is_none_or(|c| !c.is_alphanumeric())
+}
+
+fn find_link_end(chars: &[char], start: usize) -> Option<usize> {
+    let close = chars[start..].iter().position(|c| *c == ']')? + start;
+    matches!(chars.get(close + 1), Some('(' | '[')).then_some(close)
+}
+
+fn word_width(word: &Word) -> usize {
+    word.iter().map(|(text, _)| text.chars().count()).sum()
+}
+
+/// Greedily wraps words to lines of at most `width` visible characters.
+fn wrap(words: &[Word], width: usize, colored: bool) -> Vec<String> {
+    let mut lines = vec![];
+    let mut line = String::new();
+    let mut line_width = 0;
+
+    for word in words {
+        let len = word_width(word);
+        if line_width > 0 && line_width + 1 + len > width {
+            lines.push(std::mem::take(&mut line));
+            line_width = 0;
+        }
+        if line_width > 0 {
+            line.push(' ');
+            line_width += 1;
+        }
+        for (text, style) in word {
+            let codes = style.ansi(colored);
+            if codes.is_empty() {
+                line.push_str(text);
+            } else {
+                line.push_str(&codes);
+                line.push_str(text);
+                line.push_str(ANSI_RESET);
+            }
+        }
+        line_width += len;
+    }
+
+    if line_width > 0 {
+        lines.push(line);
+    }
+
+    lines
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::render;
+
+    const PUZZLE: &str = r"\--- Day 1: Historian Hysteria ---
+----------
+
+The *Chief Historian* is always present for the big Christmas sleigh launch, but nobody has seen him in months!
+
+For example:
+
+```
+3   4
+4   3
+```
+
+* Item with `code`
+* [Link](https://adventofcode.com/2024/day/1/input) text
+
+Your puzzle answer was `1234`.
+
+[1]: https://adventofcode.com
+";
+
+    #[test]
+    fn renders_layout() {
+        let rendered = render(PUZZLE, 40, false);
+        let expected = [
+            "--- Day 1: Historian Hysteria ---",
+            "",
+            "The Chief Historian is always present",
+            "for the big Christmas sleigh launch, but",
+            "nobody has seen him in months!",
+            "",
+            "For example:",
+            "",
+            "    3   4",
+            "    4   3",
+            "",
+            "• Item with code",
+            "• Link text",
+            "",
+            "Your puzzle answer was 1234.",
+            "",
+        ]
+        .join("\n");
+        assert_eq!(rendered, expected);
+    }
+
+    #[test]
+    fn wraps_to_width() {
+        let rendered = render(PUZZLE, 30, true);
+        for line in rendered.lines() {
+            let visible = strip_ansi(line);
+            assert!(
+                visible.chars().count() <= 30 || !visible.contains(' '),
+                "line exceeds width: {visible}"
+            );
+        }
+    }
+
+    #[test]
+    fn highlights_answers() {
+        let rendered = render("Your puzzle answer was `1234`.", 80, true);
+        assert!(rendered.contains("\x1b[1m\x1b[32m1234\x1b[0m"));
+    }
+
+    #[test]
+    fn renders_atx_headings() {
+        let rendered = render("## --- Part Two ---\ntext", 80, false);
+        assert_eq!(rendered, "--- Part Two ---\n\ntext\n");
+    }
+
+    fn strip_ansi(s: &str) -> String {
+        let mut out = String::new();
+        let mut in_escape = false;
+        for c in s.chars() {
+            match c {
+                '\x1b' => in_escape = true,
+                'm' if in_escape => in_escape = false,
+                _ if in_escape => {}
+                c => out.push(c),
+            }
+        }
+        out
+    }
+}
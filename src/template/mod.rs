@@ -1,14 +1,30 @@
-use std::{env, fs};
-
+pub mod alloc;
 pub mod aoc_cli;
 pub mod commands;
+pub mod config;
+pub mod differential;
+pub mod glyphs;
+pub mod image;
+pub mod input;
 pub mod runner;
+pub mod trace;
+pub mod visualize;
 
 pub use day::*;
 
+mod build;
+mod crypto;
 mod day;
+mod machine;
+mod manifest;
+mod markdown;
+pub mod puzzles;
 mod readme_benchmarks;
+mod report;
 mod run_multi;
+mod rusage;
+mod sha256;
+mod terminal;
 mod timings;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
@@ -16,53 +32,175 @@ pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
 
 /// Helper function that reads a text file to a string.
+/// The contents are validated and normalized by [`input::load`].
 #[must_use]
 pub fn read_file(folder: &str, day: Day) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    input::load(folder, &format!("{day}.txt"), day).unwrap_or_else(|e| panic!("{e}"))
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
 pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
-    let cwd = env::current_dir().unwrap();
-    let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    input::load(folder, &format!("{day}-{part}.txt"), day).unwrap_or_else(|e| panic!("{e}"))
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Sets up the binary of a day: its `main` runs the parts with the `run_parts` function of the day module,
+/// see [`day_module!`].
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
 #[macro_export]
 macro_rules! solution {
     ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+        $crate::solution!(@impl $day, [1, 2]);
     };
     ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+        $crate::solution!(@impl $day, [1]);
     };
     ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+        $crate::solution!(@impl $day, [2]);
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
-        /// The current day.
-        const DAY: $crate::template::Day = $crate::day!($day);
-
+    (@impl $day:expr, [$( $part:expr ),*]) => {
         #[cfg(feature = "dhat-heap")]
         #[global_allocator]
         static ALLOC: dhat::Alloc = dhat::Alloc;
 
+        #[cfg(all(feature = "alloc-stats", not(feature = "dhat-heap")))]
+        #[global_allocator]
+        static ALLOC: $crate::template::alloc::CountingAlloc = $crate::template::alloc::CountingAlloc;
+
         fn main() {
+            // the day module is glob-imported by the binary.
+            const _: () = assert!(DAY.into_inner() == $day, "the binary runs another day");
+            run_parts(&[$( $part ),*]);
+        }
+    };
+}
+
+/// Declares the constant `DAY` of a day module and how its parts are run, by the binary and in-process.
+///
+/// Each part reads `data/inputs/XX-<part>.txt` if it exists and `data/inputs/XX.txt` otherwise,
+/// see [`input::PartInputs`].
+///
+/// With `parse = <fn>`, the input is parsed once by `<fn>` and both parts receive a reference to its output,
+/// e.g. `day_module!(1, parse = parse)` for `fn parse(input: &str) -> Lists` and `fn part_one(lists: &Lists)`.
+/// Parsing is timed separately from the parts.
+#[macro_export]
+macro_rules! day_module {
+    ($day:expr) => {
+        $crate::day_module!(@setup $day);
+
+        /// Runs `parts` against the real input and prints their results.
+        pub fn run_parts(parts: &[u8]) {
+            use $crate::template::runner::*;
+            #[cfg(feature = "trace")]
+            $crate::template::trace::init(DAY);
+            let inputs = $crate::template::input::PartInputs::load(DAY, parts);
+            for &part in parts {
+                match part {
+                    1 => run_part(part_one, inputs.for_part(1), DAY, 1),
+                    _ => run_part(part_two, inputs.for_part(2), DAY, 2),
+                }
+            }
+        }
+
+        /// Solves one part once, see [`solutions!`](crate::solutions).
+        #[cfg(feature = "solutions")]
+        pub fn solve_part(part: u8, input: &str) -> $crate::template::runner::Solved {
+            let (result, duration) =
+                $crate::template::runner::solve_once(part_one, part_two, input, part);
+            $crate::template::runner::Solved {
+                result,
+                parse: None,
+                duration,
+            }
+        }
+    };
+
+    ($day:expr, parse = $parse:expr) => {
+        $crate::day_module!(@setup $day);
+
+        /// Runs `parts` against the real input and prints their results.
+        pub fn run_parts(parts: &[u8]) {
             use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            #[cfg(feature = "trace")]
+            $crate::template::trace::init(DAY);
+            let inputs = $crate::template::input::PartInputs::load(DAY, parts);
+            if let Some(input) = inputs.shared() {
+                let parsed = run_parse($parse, input, DAY);
+                for &part in parts {
+                    $crate::day_module!(@run_part part, &parsed);
+                }
+            } else {
+                // parts with their own input also parse it on their own.
+                for &part in parts {
+                    let parsed = run_parse($parse, inputs.for_part(part), DAY);
+                    $crate::day_module!(@run_part part, &parsed);
+                }
+            }
+        }
+
+        /// Solves one part once, see [`solutions!`](crate::solutions).
+        #[cfg(feature = "solutions")]
+        pub fn solve_part(part: u8, input: &str) -> $crate::template::runner::Solved {
+            let timer = std::time::Instant::now();
+            let parsed = $parse(input);
+            let parse = timer.elapsed();
+            let (result, duration) = $crate::template::runner::solve_once(
+                |parsed| part_one(parsed),
+                |parsed| part_two(parsed),
+                &parsed,
+                part,
+            );
+            $crate::template::runner::Solved {
+                result,
+                parse: Some(parse),
+                duration,
+            }
+        }
+    };
+
+    // the parts are called from closures, so `&Vec<T>` coerces to e.g. `&[T]` like in a normal call.
+    (@run_part $part:expr, $parsed:expr) => {
+        match $part {
+            1 => run_part(|parsed| part_one(parsed), $parsed, DAY, 1),
+            _ => run_part(|parsed| part_two(parsed), $parsed, DAY, 2),
+        }
+    };
+
+    (@setup $day:expr) => {
+        /// The day this module solves.
+        pub const DAY: $crate::template::Day = $crate::day!($day);
+    };
+}
+
+/// Registers the day modules so they can be solved in-process, e.g. by `cargo serve`.
+///
+/// With the `solutions` feature, generates the constant `DAYS` and `fn solve(day, part, input)`.
+/// Without it, nothing refers to the solutions, so the CLI does not link them and their dependencies.
+#[macro_export]
+macro_rules! solutions {
+    ($( $module:ident ),* $(,)?) => {
+        /// The days that can be solved with [`solve`].
+        #[cfg(feature = "solutions")]
+        pub const DAYS: &[$crate::template::Day] = &[$( $module::DAY ),*];
+
+        /// Solves one part of a day once. `None` if the day is not registered or the part is not 1 or 2.
+        #[cfg(feature = "solutions")]
+        pub fn solve(
+            day: $crate::template::Day,
+            part: u8,
+            input: &str,
+        ) -> Option<$crate::template::runner::Solved> {
+            if part != 1 && part != 2 {
+                return None;
+            }
+            $(
+                if day == $module::DAY {
+                    return Some($module::solve_part(part, input));
+                }
+            )*
+            None
         }
     };
 }
@@ -0,0 +1,496 @@
+/// A local archive of the puzzle descriptions downloaded to `data/puzzles`.
+///
+/// Descriptions of the configured year live in `data/puzzles/XX.md`, descriptions of other years
+/// can be kept in `data/puzzles/<year>/XX.md`. Tags are stored in `data/puzzles/tags.json`.
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap},
+    fmt::Display,
+    fs, io,
+    path::{Path, PathBuf},
+    str::FromStr,
+};
+
+use tinyjson::JsonValue;
+
+use crate::template::{config, markdown, Day, ANSI_BOLD, ANSI_RESET};
+
+const TAGS_FILE_NAME: &str = "tags.json";
+const ANSI_YELLOW: &str = "\x1b[33m";
+
+/// Weight of a match in the puzzle title compared to a match in the description.
+const TITLE_WEIGHT: usize = 5;
+const MAX_SNIPPETS: usize = 3;
+const SNIPPET_CONTEXT: usize = 40;
+
+/// Identifies a puzzle across years.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PuzzleId {
+    pub year: Option<u16>,
+    pub day: Day,
+}
+
+impl Display for PuzzleId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.year {
+            Some(year) => write!(f, "{year} Day {}", self.day),
+            None => write!(f, "Day {}", self.day),
+        }
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Puzzle {
+    pub id: PuzzleId,
+    pub path: PathBuf,
+}
+
+impl Puzzle {
+    pub fn description(&self) -> io::Result<String> {
+        fs::read_to_string(&self.path)
+    }
+}
+
+/// Extracts the title of a puzzle, e.g. `Guard Gallivant` from `--- Day 6: Guard Gallivant ---`.
+pub fn title(description: &str) -> Option<String> {
+    description.lines().find_map(|line| {
+        let line = line.trim().trim_start_matches('#').trim().replace('\\', "");
+        let rest = line.strip_prefix("--- Day ")?;
+        let (_, title) = rest.split_once(':')?;
+        Some(title.trim().trim_end_matches('-').trim().to_string())
+    })
+}
+
+/// Extracts the answers of solved parts, e.g. `1234` from ``Your puzzle answer was `1234`.``
+/// They are part of the description once a part is solved and the puzzle is downloaded again.
+pub fn answers(description: &str) -> Vec<String> {
+    description
+        .lines()
+        .filter_map(|line| {
+            let answer = line.trim().strip_prefix("Your puzzle answer was")?;
+            let answer = answer.trim().trim_end_matches('.').trim_matches('`').trim();
+            Some(answer.to_string())
+        })
+        .collect()
+}
+
+/// Lists all downloaded puzzles, sorted by year and day.
+pub fn discover() -> Vec<Puzzle> {
+    let config = config::get();
+    let dir = &config.puzzles_dir;
+    let mut puzzles = list_dir(dir, config.year());
+
+    if let Ok(entries) = fs::read_dir(dir) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let year = path
+                .file_name()
+                .and_then(|n| n.to_str())
+                .and_then(|n| n.parse::<u16>().ok());
+            if let (true, Some(year)) = (path.is_dir(), year) {
+                puzzles.extend(list_dir(&path, Some(year)));
+            }
+        }
+    }
+
+    puzzles.sort_unstable_by_key(|p| p.id);
+    puzzles.dedup_by_key(|p| p.id);
+    puzzles
+}
+
+fn list_dir(dir: &Path, year: Option<u16>) -> Vec<Puzzle> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return vec![];
+    };
+
+    entries
+        .flatten()
+        .filter_map(|entry| {
+            let path = entry.path();
+            if path.extension()? != "md" {
+                return None;
+            }
+            let day = path.file_stem()?.to_str()?.parse::<Day>().ok()?;
+            Some(Puzzle {
+                id: PuzzleId { year, day },
+                path,
+            })
+        })
+        .collect()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[derive(Clone, Debug)]
+pub struct SearchResult {
+    pub id: PuzzleId,
+    pub title: Option<String>,
+    pub score: usize,
+    pub snippets: Vec<String>,
+}
+
+/// Case-insensitive full-text search. Every term of the query has to match.
+/// Results are ranked by the number of matches, matches in the title count more.
+pub fn search(puzzles: &[(PuzzleId, String)], query: &str, highlight: bool) -> Vec<SearchResult> {
+    let terms: Vec<String> = query.split_whitespace().map(str::to_lowercase).collect();
+
+    if terms.is_empty() {
+        return vec![];
+    }
+
+    let mut results: Vec<SearchResult> = puzzles
+        .iter()
+        .filter_map(|(id, description)| {
+            let title = title(description);
+            let text = markdown::render(description, usize::MAX, false);
+            let text_lower = text.to_lowercase();
+            let title_lower = title.as_deref().unwrap_or_default().to_lowercase();
+
+            let mut score = 0;
+            for term in &terms {
+                let count = text_lower.matches(term.as_str()).count();
+                if count == 0 {
+                    return None;
+                }
+                score += count + title_lower.matches(term.as_str()).count() * TITLE_WEIGHT;
+            }
+
+            let snippets = text
+                .lines()
+                .filter(|line| {
+                    let lower = line.to_lowercase();
+                    !lower.contains("--- day ") && terms.iter().any(|t| lower.contains(t.as_str()))
+                })
+                .take(MAX_SNIPPETS)
+                .map(|line| snippet(line, &terms, highlight))
+                .collect();
+
+            Some(SearchResult {
+                id: *id,
+                title,
+                score,
+                snippets,
+            })
+        })
+        .collect();
+
+    results.sort_by(|a, b| b.score.cmp(&a.score).then(a.id.cmp(&b.id)));
+    results
+}
+
+/// Cuts a line down to the context around the first match and highlights all matches.
+fn snippet(line: &str, terms: &[String], highlight: bool) -> String {
+    let chars: Vec<char> = line.chars().collect();
+    let lower: Vec<char> = line.to_lowercase().chars().collect();
+
+    // lowercasing can change the length of some characters, don't highlight these lines.
+    if chars.len() != lower.len() {
+        return line.to_string();
+    }
+
+    let mut marked = vec![false; chars.len()];
+    for term in terms {
+        let term: Vec<char> = term.chars().collect();
+        for start in 0..=lower.len().saturating_sub(term.len()) {
+            if lower[start..].starts_with(&term) {
+                marked[start..start + term.len()].fill(true);
+            }
+        }
+    }
+
+    let first = marked.iter().position(|m| *m).unwrap_or(0);
+    let start = first.saturating_sub(SNIPPET_CONTEXT);
+    let end = (first + SNIPPET_CONTEXT * 2).min(chars.len());
+
+    let mut out = String::new();
+    if start > 0 {
+        out.push('…');
+    }
+    for idx in start..end {
+        let is_start = marked[idx] && (idx == start || !marked[idx - 1]);
+        let is_end = marked[idx] && (idx + 1 == end || !marked[idx + 1]);
+        if highlight && is_start {
+            out.push_str(ANSI_BOLD);
+            out.push_str(ANSI_YELLOW);
+        }
+        out.push(chars[idx]);
+        if highlight && is_end {
+            out.push_str(ANSI_RESET);
+        }
+    }
+    if end < chars.len() {
+        out.push('…');
+    }
+    out
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Maps puzzles to user-defined tags like `grid` or `bfs`.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TagIndex {
+    pub tags: BTreeMap<PuzzleId, BTreeSet<String>>,
+}
+
+impl TagIndex {
+    fn path() -> PathBuf {
+        config::get().puzzles_dir.join(TAGS_FILE_NAME)
+    }
+
+    /// Rehydrate the index from its JSON file. If not present, returns an empty index.
+    pub fn read_from_file() -> Result<Self, String> {
+        match fs::read_to_string(Self::path()) {
+            Ok(s) => TagIndex::try_from(s),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(TagIndex::default()),
+            Err(e) => Err(e.to_string()),
+        }
+    }
+
+    /// Dehydrate the index to its JSON file.
+    pub fn store_file(&self) -> Result<(), io::Error> {
+        let json = JsonValue::from(self.clone());
+        fs::create_dir_all(&config::get().puzzles_dir)?;
+        let mut file = fs::File::create(Self::path())?;
+        json.format_to(&mut file)
+    }
+
+    pub fn add(&mut self, id: PuzzleId, tags: &[String]) {
+        self.tags
+            .entry(id)
+            .or_default()
+            .extend(tags.iter().map(|t| normalize_tag(t)));
+    }
+
+    pub fn remove(&mut self, id: PuzzleId, tags: &[String]) {
+        if let Some(existing) = self.tags.get_mut(&id) {
+            for tag in tags {
+                existing.remove(&normalize_tag(tag));
+            }
+            if existing.is_empty() {
+                self.tags.remove(&id);
+            }
+        }
+    }
+
+    pub fn get(&self, id: PuzzleId) -> Option<&BTreeSet<String>> {
+        self.tags.get(&id)
+    }
+
+    /// Puzzles that have all of the given tags.
+    pub fn find(&self, tags: &[String]) -> Vec<PuzzleId> {
+        let tags: Vec<String> = tags.iter().map(|t| normalize_tag(t)).collect();
+        self.tags
+            .iter()
+            .filter(|(_, t)| tags.iter().all(|tag| t.contains(tag)))
+            .map(|(id, _)| *id)
+            .collect()
+    }
+
+    /// All tags with the puzzles they are assigned to.
+    pub fn by_tag(&self) -> BTreeMap<&str, Vec<PuzzleId>> {
+        let mut map: BTreeMap<&str, Vec<PuzzleId>> = BTreeMap::new();
+        for (id, tags) in &self.tags {
+            for tag in tags {
+                map.entry(tag).or_default().push(*id);
+            }
+        }
+        map
+    }
+}
+
+fn normalize_tag(tag: &str) -> String {
+    tag.trim().to_lowercase()
+}
+
+/// Tags of puzzles without a known year are stored under this key.
+const UNKNOWN_YEAR_KEY: &str = "unknown";
+
+impl From<TagIndex> for JsonValue {
+    fn from(value: TagIndex) -> Self {
+        let mut years: HashMap<String, JsonValue> = HashMap::new();
+
+        for (id, tags) in value.tags {
+            let year = id
+                .year
+                .map_or_else(|| UNKNOWN_YEAR_KEY.into(), |y| y.to_string());
+
+            if let JsonValue::Object(days) = years
+                .entry(year)
+                .or_insert_with(|| JsonValue::Object(HashMap::new()))
+            {
+                days.insert(
+                    id.day.to_string(),
+                    JsonValue::Array(tags.into_iter().map(JsonValue::String).collect()),
+                );
+            }
+        }
+
+        JsonValue::Object(years)
+    }
+}
+
+impl TryFrom<String> for TagIndex {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+        let years = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        let mut index = TagIndex::default();
+
+        for (year, days) in years {
+            let year = match year.as_str() {
+                UNKNOWN_YEAR_KEY => None,
+                y => Some(
+                    y.parse::<u16>()
+                        .map_err(|_| format!("invalid year `{y}`."))?,
+                ),
+            };
+
+            let days = days
+                .get::<HashMap<String, JsonValue>>()
+                .ok_or("expected years to be objects.")?;
+
+            for (day, tags) in days {
+                let day = Day::from_str(day).map_err(|_| format!("invalid day `{day}`."))?;
+                let tags = tags
+                    .get::<Vec<JsonValue>>()
+                    .ok_or("expected tags to be an array.")?
+                    .iter()
+                    .map(|t| t.get::<String>().cloned())
+                    .collect::<Option<BTreeSet<_>>>()
+                    .ok_or("expected tags to be strings.")?;
+                index.tags.insert(PuzzleId { year, day }, tags);
+            }
+        }
+
+        Ok(index)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{answers, search, snippet, title, PuzzleId, TagIndex};
+    use crate::day;
+
+    fn id(year: u16, day: u8) -> PuzzleId {
+        PuzzleId {
+            year: Some(year),
+            day: crate::template::Day::new(day).unwrap(),
+        }
+    }
+
+    fn get_mock_puzzles() -> Vec<(PuzzleId, String)> {
+        vec![
+            (
+                id(2024, 6),
+                "\\--- Day 6: Guard Gallivant ---\n----------\n\nThe guard walks a *grid*.\n".into(),
+            ),
+            (
+                id(2024, 8),
+                "\\--- Day 8: Resonant Collinearity ---\n----------\n\nEach antinode of the grid is an *antinode*.\n\nCount antinodes.\n".into(),
+            ),
+            (
+                id(2023, 10),
+                "\\--- Day 10: Pipe Maze ---\n----------\n\nThe pipes form a loop.\n".into(),
+            ),
+        ]
+    }
+
+    #[test]
+    fn extracts_titles() {
+        assert_eq!(
+            title("\\--- Day 6: Guard Gallivant ---\n----------\n"),
+            Some("Guard Gallivant".into())
+        );
+        assert_eq!(
+            title("## --- Day 13: Claw Contraption ---\n"),
+            Some("Claw Contraption".into())
+        );
+        assert_eq!(title("no title"), None);
+    }
+
+    #[test]
+    fn extracts_answers() {
+        let description = "--- Day 1 ---\n\nYour puzzle answer was `1234`.\n\n--- Part Two ---\n\nYour puzzle answer was `ABCD`.\n";
+        assert_eq!(answers(description), vec!["1234", "ABCD"]);
+        assert!(answers("--- Day 1 ---\n").is_empty());
+    }
+
+    #[test]
+    fn searches_case_insensitive() {
+        let results = search(&get_mock_puzzles(), "ANTINODE", false);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].id, id(2024, 8));
+        assert_eq!(results[0].score, 3);
+        assert_eq!(results[0].title, Some("Resonant Collinearity".into()));
+    }
+
+    #[test]
+    fn ranks_results() {
+        let results = search(&get_mock_puzzles(), "grid", false);
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].id, id(2024, 6));
+        assert_eq!(results[1].id, id(2024, 8));
+    }
+
+    #[test]
+    fn requires_all_terms() {
+        assert_eq!(search(&get_mock_puzzles(), "grid guard", false).len(), 1);
+        assert_eq!(search(&get_mock_puzzles(), "grid pipes", false).len(), 0);
+    }
+
+    #[test]
+    fn ranks_title_matches_higher() {
+        let results = search(&get_mock_puzzles(), "pipe", false);
+        assert_eq!(results[0].score, 2 + 5);
+    }
+
+    #[test]
+    fn highlights_snippets() {
+        let s = snippet("Count Antinodes.", &["antinode".into()], true);
+        assert_eq!(s, "Count \x1b[1m\x1b[33mAntinode\x1b[0ms.");
+    }
+
+    #[test]
+    fn maintains_tags() {
+        let mut index = TagIndex::default();
+        index.add(id(2024, 6), &["Grid".into(), "simulation".into()]);
+        index.add(id(2024, 10), &["grid".into(), "bfs".into()]);
+
+        assert_eq!(
+            index.find(&["grid".into()]),
+            vec![id(2024, 6), id(2024, 10)]
+        );
+        assert_eq!(
+            index.find(&["grid".into(), "BFS".into()]),
+            vec![id(2024, 10)]
+        );
+
+        index.remove(id(2024, 10), &["grid".into(), "bfs".into()]);
+        assert!(index.get(id(2024, 10)).is_none());
+        assert_eq!(index.by_tag().get("grid"), Some(&vec![id(2024, 6)]));
+    }
+
+    #[test]
+    fn roundtrips_json() {
+        let mut index = TagIndex::default();
+        index.add(id(2024, 6), &["grid".into()]);
+        index.add(
+            PuzzleId {
+                year: None,
+                day: day!(1),
+            },
+            &["sorting".into()],
+        );
+
+        let json = tinyjson::JsonValue::from(index.clone())
+            .stringify()
+            .unwrap();
+        assert_eq!(TagIndex::try_from(json).unwrap(), index);
+    }
+}
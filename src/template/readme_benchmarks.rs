@@ -1,9 +1,12 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+use std::{collections::HashMap, fs, io};
 
-use crate::template::timings::Timings;
-use crate::template::Day;
+use crate::template::alloc::format_bytes;
+use crate::template::config::Column;
+use crate::template::machine::Machine;
+use crate::template::timings::{Timing, Timings};
+use crate::template::{config, puzzles, Day};
 
 static MARKER: &str = "<!--- benchmarking table --->";
 
@@ -26,8 +29,8 @@ pub struct TablePosition {
 }
 
 #[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+pub fn get_path_for_module(day: Day) -> String {
+    format!("./src/days/day{day}.rs")
 }
 
 fn locate_table(readme: &str) -> Result<TablePosition, Error> {
@@ -52,107 +55,266 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
-
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
-
-    for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
-        lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
+/// What the table shows about a day besides its timings, read from its puzzle description and solution.
+#[derive(Clone, Debug, Default)]
+pub struct DayInfo {
+    pub title: Option<String>,
+    /// The puzzle page, if the year is known.
+    pub url: Option<String>,
+    pub stars: usize,
+    pub lines_of_code: Option<usize>,
+}
+
+impl DayInfo {
+    fn read(day: Day) -> Self {
+        let config = config::get();
+        let description = fs::read_to_string(config.puzzle_path(day)).ok();
+
+        DayInfo {
+            title: description.as_deref().and_then(puzzles::title),
+            url: config
+                .year()
+                .map(|year| format!("https://adventofcode.com/{year}/day/{}", day.into_inner())),
+            stars: description.map_or(0, |d| puzzles::answers(&d).len()),
+            lines_of_code: fs::read_to_string(get_path_for_module(day))
+                .ok()
+                .map(|source| count_lines_of_code(&source)),
+        }
+    }
+}
+
+/// Counts the lines of a solution, without blank lines, comments and the tests at the end.
+fn count_lines_of_code(source: &str) -> usize {
+    source
+        .lines()
+        .map(str::trim)
+        .take_while(|line| !line.starts_with("#[cfg(test)]"))
+        .filter(|line| !line.is_empty() && !line.starts_with("//"))
+        .count()
+}
+
+fn header(column: Column) -> &'static str {
+    match column {
+        Column::Day => "Day",
+        Column::Title => "Puzzle",
+        Column::Parse => "Parse",
+        Column::Part1 => "Part 1",
+        Column::Part2 => "Part 2",
+        Column::Stars => "Stars",
+        Column::Memory => "Memory",
+        Column::LinesOfCode => "LOC",
+    }
+}
+
+fn cell(column: Column, timing: &Timing, info: Option<&DayInfo>) -> String {
+    let code = |value: Option<String>| format!("`{}`", value.unwrap_or_else(|| "-".into()));
+
+    match column {
+        Column::Day => format!(
+            "[Day {}]({})",
             timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+            get_path_for_module(timing.day)
+        ),
+        Column::Title => match info.map(|info| (&info.title, &info.url)) {
+            Some((Some(title), Some(url))) => format!("[{title}]({url})"),
+            Some((Some(title), None)) => title.clone(),
+            _ => "-".into(),
+        },
+        Column::Parse => code(timing.parse.clone()),
+        Column::Part1 => code(timing.part_1.clone()),
+        Column::Part2 => code(timing.part_2.clone()),
+        Column::Stars => match info.map_or(0, |info| info.stars) {
+            0 => "-".into(),
+            stars => "⭐".repeat(stars),
+        },
+        Column::Memory => code(
+            [&timing.part_1_usage, &timing.part_2_usage]
+                .into_iter()
+                .filter_map(|usage| usage.map(|usage| usage.peak_rss))
+                .max()
+                .map(format_bytes),
+        ),
+        Column::LinesOfCode => info
+            .and_then(|info| info.lines_of_code)
+            .map_or_else(|| "-".into(), |lines| lines.to_string()),
+    }
+}
+
+fn construct_table(
+    prefix: &str,
+    timings: Timings,
+    total_millis: f64,
+    machine: Option<&Machine>,
+    columns: &[Column],
+    infos: &HashMap<Day, DayInfo>,
+) -> String {
+    let header_line = format!("{prefix} Benchmarks");
+
+    // only show parse times once a solution uses a shared `parse` function, and titles once a puzzle is downloaded.
+    let has_parse = timings.data.iter().any(|t| t.parse.is_some());
+    let has_title = infos.values().any(|info| info.title.is_some());
+    let columns: Vec<Column> = columns
+        .iter()
+        .copied()
+        .filter(|column| match column {
+            Column::Parse => has_parse,
+            Column::Title => has_title,
+            _ => true,
+        })
+        .collect();
+
+    let mut lines: Vec<String> = vec![MARKER.into(), header_line, String::new()];
+
+    let headers: Vec<&str> = columns.iter().map(|column| header(*column)).collect();
+    lines.push(format!("| {} |", headers.join(" | ")));
+    lines.push(format!("| {}  |", vec![":---:"; columns.len()].join(" | ")));
+
+    for timing in &timings.data {
+        let info = infos.get(&timing.day);
+        let cells: Vec<String> = columns
+            .iter()
+            .map(|column| cell(*column, timing, info))
+            .collect();
+        lines.push(format!("| {} |", cells.join(" | ")));
     }
 
     lines.push(String::new());
     lines.push(format!("**Total: {total_millis:.2}ms**"));
+
+    if let Some(machine) = machine {
+        lines.push(String::new());
+        lines.push(format!("_Measured on {machine}._"));
+    }
+
     lines.push(MARKER.into());
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
+fn update_content(
+    s: &mut String,
+    timings: Timings,
+    total_millis: f64,
+    machine: Option<&Machine>,
+    columns: &[Column],
+    infos: &HashMap<Day, DayInfo>,
+) -> Result<(), Error> {
     let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+    let table = construct_table("##", timings, total_millis, machine, columns, infos);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
-pub fn update(timings: Timings) -> Result<(), Error> {
-    let path = "README.md";
+pub fn update(timings: Timings, machine: &Machine) -> Result<(), Error> {
+    let config = config::get();
+    let path = &config.readme_path;
     let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
     let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
+    let infos: HashMap<Day, DayInfo> = timings
+        .data
+        .iter()
+        .map(|timing| (timing.day, DayInfo::read(timing.day)))
+        .collect();
+    update_content(
+        &mut readme,
+        timings,
+        total_millis,
+        Some(machine),
+        &config.columns,
+        &infos,
+    )?;
     fs::write(path, &readme)?;
     Ok(())
 }
 
 #[cfg(feature = "test_lib")]
 mod tests {
-    use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use std::collections::HashMap;
+
+    use super::{count_lines_of_code, update_content, DayInfo, MARKER};
+    use crate::{
+        day,
+        template::config::{Column, DEFAULT_COLUMNS},
+        template::machine::Machine,
+        template::rusage::Usage,
+        template::timings::Timing,
+        template::timings::Timings,
+    };
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
                     day: day!(1),
+                    parse: None,
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
+                    part_1_allocs: None,
+                    part_2_allocs: None,
+                    part_1_usage: None,
+                    part_2_usage: None,
                     total_nanos: 3e+10,
                 },
                 Timing {
                     day: day!(2),
+                    parse: None,
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
+                    part_1_allocs: None,
+                    part_2_allocs: None,
+                    part_1_usage: None,
+                    part_2_usage: None,
                     total_nanos: 7e+10,
                 },
                 Timing {
                     day: day!(4),
+                    parse: None,
                     part_1: Some("40ms".into()),
                     part_2: Some("50ms".into()),
+                    part_1_allocs: None,
+                    part_2_allocs: None,
+                    part_1_usage: None,
+                    part_2_usage: None,
                     total_nanos: 9e+10,
                 },
             ],
         }
     }
 
+    fn update_with_defaults(
+        s: &mut String,
+        timings: Timings,
+        machine: Option<&Machine>,
+    ) -> Result<(), super::Error> {
+        update_content(s, timings, 190.0, machine, &DEFAULT_COLUMNS, &HashMap::new())
+    }
+
     #[test]
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_with_defaults(&mut s, get_mock_timings(), None).unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_with_defaults(&mut s, get_mock_timings(), None).unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_with_defaults(&mut s, get_mock_timings(), None).unwrap();
         assert_eq!(s.contains("## Benchmarks"), true);
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_with_defaults(&mut s, get_mock_timings(), None).unwrap();
+        update_with_defaults(&mut s, get_mock_timings(), None).unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -160,7 +322,7 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_with_defaults(&mut s, get_mock_timings(), None).unwrap();
         let expected = [
             "foo",
             "bar",
@@ -169,9 +331,9 @@ mod tests {
             "",
             "| Day | Part 1 | Part 2 |",
             "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| [Day 1](./src/days/day01.rs) | `10ms` | `20ms` |",
+            "| [Day 2](./src/days/day02.rs) | `30ms` | `40ms` |",
+            "| [Day 4](./src/days/day04.rs) | `40ms` | `50ms` |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -180,4 +342,94 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_parse_column() {
+        let mut timings = get_mock_timings();
+        timings.data[0].parse = Some("5ms".into());
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_with_defaults(&mut s, timings, None).unwrap();
+        assert!(s.contains("| Day | Parse | Part 1 | Part 2 |"));
+        assert!(s.contains("| [Day 1](./src/days/day01.rs) | `5ms` | `10ms` | `20ms` |"));
+        assert!(s.contains("| [Day 2](./src/days/day02.rs) | `-` | `30ms` | `40ms` |"));
+    }
+
+    #[test]
+    fn format_title_column() {
+        let infos = HashMap::from([
+            (
+                day!(1),
+                DayInfo {
+                    title: Some("Historian Hysteria".into()),
+                    url: Some("https://adventofcode.com/2024/day/1".into()),
+                    ..DayInfo::default()
+                },
+            ),
+            (
+                day!(2),
+                DayInfo {
+                    title: Some("Red-Nosed Reports".into()),
+                    ..DayInfo::default()
+                },
+            ),
+        ]);
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(
+            &mut s,
+            get_mock_timings(),
+            190.0,
+            None,
+            &DEFAULT_COLUMNS,
+            &infos,
+        )
+        .unwrap();
+        assert!(s.contains("| Day | Puzzle | Part 1 | Part 2 |"));
+        assert!(s.contains("| [Day 1](./src/days/day01.rs) | [Historian Hysteria](https://adventofcode.com/2024/day/1) | `10ms` | `20ms` |"));
+        assert!(s.contains("| [Day 2](./src/days/day02.rs) | Red-Nosed Reports | `30ms` | `40ms` |"));
+        assert!(s.contains("| [Day 4](./src/days/day04.rs) | - | `40ms` | `50ms` |"));
+    }
+
+    #[test]
+    fn format_optional_columns() {
+        let mut timings = get_mock_timings();
+        timings.data[0].part_2_usage = Some(Usage {
+            peak_rss: 3 * 1024 * 1024,
+            ..Usage::default()
+        });
+        let infos = HashMap::from([(
+            day!(1),
+            DayInfo {
+                stars: 2,
+                lines_of_code: Some(42),
+                ..DayInfo::default()
+            },
+        )]);
+        let columns = [
+            Column::Day,
+            Column::Stars,
+            Column::Memory,
+            Column::LinesOfCode,
+        ];
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_content(&mut s, timings, 190.0, None, &columns, &infos).unwrap();
+        assert!(s.contains("| Day | Stars | Memory | LOC |\n| :---: | :---: | :---: | :---:  |"));
+        assert!(s.contains("| [Day 1](./src/days/day01.rs) | ⭐⭐ | `3.0 MiB` | 42 |"));
+        assert!(s.contains("| [Day 2](./src/days/day02.rs) | - | `-` | - |"));
+    }
+
+    #[test]
+    fn counts_lines_of_code() {
+        let source = "/// docs\nuse std::fs;\n\n// comment\npub fn part_one() {}\n\n#[cfg(test)]\nmod tests {}\n";
+        assert_eq!(count_lines_of_code(source), 2);
+    }
+
+    #[test]
+    fn format_machine_footer() {
+        let machine = Machine::mock("Apple M1");
+        let mut s = format!("{}{}", MARKER, MARKER);
+        update_with_defaults(&mut s, get_mock_timings(), Some(&machine)).unwrap();
+        assert!(s.contains(
+            "**Total: 190.00ms**\n\n_Measured on Apple M1 (8 cores), rustc 1.83.0, `release` profile, commit `1a2b3c4`._\n<!--- benchmarking table --->"
+        ));
+    }
 }
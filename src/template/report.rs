@@ -0,0 +1,487 @@
+/// Classifies the result of every part run by `cargo all` and `cargo time`, and writes JUnit XML or TAP reports for CI.
+use std::{collections::BTreeMap, fmt::Display, fs, io, path::Path};
+
+use crate::template::{
+    config, puzzles,
+    run_multi::child_commands::{parse_time, Execution},
+    Day,
+};
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Status {
+    Passed,
+    /// The part returned `None`, like the scaffolded template does.
+    NotImplemented,
+    Panicked(String),
+    BuildError(String),
+    TimedOut,
+    /// The answer differs from the one in the stored puzzle description.
+    WrongAnswer {
+        expected: String,
+        actual: String,
+    },
+}
+
+impl Status {
+    /// Whether the part should fail a CI run.
+    pub fn is_failure(&self) -> bool {
+        !matches!(self, Status::Passed | Status::NotImplemented)
+    }
+
+    fn name(&self) -> &'static str {
+        match self {
+            Status::Passed => "passed",
+            Status::NotImplemented => "not implemented",
+            Status::Panicked(_) => "panicked",
+            Status::BuildError(_) => "build error",
+            Status::TimedOut => "timed out",
+            Status::WrongAnswer { .. } => "wrong answer",
+        }
+    }
+
+    fn detail(&self) -> Option<String> {
+        match self {
+            Status::Panicked(message) | Status::BuildError(message) if !message.is_empty() => {
+                Some(message.clone())
+            }
+            Status::WrongAnswer { expected, actual } => {
+                Some(format!("expected {expected}, got {actual}"))
+            }
+            _ => None,
+        }
+    }
+}
+
+impl Display for Status {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.detail() {
+            Some(detail) => write!(f, "{}: {detail}", self.name()),
+            None => write!(f, "{}", self.name()),
+        }
+    }
+}
+
+/// The result of running one part of a day.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PartResult {
+    pub day: Day,
+    pub part: u8,
+    pub status: Status,
+    /// Time as printed by the runner, e.g. `12.0µs`.
+    pub time: Option<String>,
+    pub nanos: Option<f64>,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Report {
+    pub results: Vec<PartResult>,
+}
+
+/// A part line printed by the runner, e.g. `Part 1: 42 (12.0µs)`.
+#[derive(Debug, PartialEq)]
+struct PrintedPart {
+    answer: Option<String>,
+    time: Option<(String, f64)>,
+    complete: bool,
+}
+
+/// Answers of the solved parts of a day, read from its stored puzzle description.
+pub fn expected_answers(day: Day) -> Vec<String> {
+    fs::read_to_string(config::get().puzzle_path(day))
+        .map(|description| puzzles::answers(&description))
+        .unwrap_or_default()
+}
+
+/// Classifies both parts of a day from the execution of its solution bin.
+/// Parts that were not printed by a successful run, e.g. for `solution!(1, 1)`, are left out.
+pub fn classify(day: Day, execution: &Execution, expected: &[String]) -> Vec<PartResult> {
+    let (stdout, failure) = match execution {
+        Execution::NotScaffolded => return vec![],
+        Execution::BuildFailed { stderr } => (
+            &[][..],
+            Some(Status::BuildError(build_error(stderr))),
+        ),
+        Execution::TimedOut { stdout, .. } => (stdout.as_slice(), Some(Status::TimedOut)),
+        Execution::Finished {
+            stdout,
+            stderr,
+            success,
+        } => (
+            stdout.as_slice(),
+            (!success).then(|| Status::Panicked(panic_message(stderr))),
+        ),
+    };
+
+    let printed = parse_parts(stdout);
+
+    let mut results: Vec<PartResult> = (1..=2)
+        .filter_map(|part| {
+            let printed = printed.get(&part);
+            let status = match printed {
+                Some(PrintedPart {
+                    complete: true,
+                    answer: None,
+                    ..
+                }) => Status::NotImplemented,
+                Some(PrintedPart {
+                    complete: true,
+                    answer: Some(actual),
+                    ..
+                }) => match expected.get(usize::from(part) - 1) {
+                    // unrecognized letter art is printed without an answer.
+                    Some(expected) if !actual.is_empty() && expected != actual => {
+                        Status::WrongAnswer {
+                            expected: expected.clone(),
+                            actual: actual.clone(),
+                        }
+                    }
+                    _ => Status::Passed,
+                },
+                // the run stopped before the part finished, or the part is not run at all.
+                _ => failure.clone()?,
+            };
+
+            let time = printed.and_then(|p| p.time.clone());
+            Some(PartResult {
+                day,
+                part,
+                status,
+                time: time.as_ref().map(|t| t.0.clone()),
+                nanos: time.map(|t| t.1),
+            })
+        })
+        .collect();
+
+    // the run can still fail after all parts printed their answers, e.g. in a `Drop` or with `process::exit`.
+    if let Some(failure) = failure {
+        if !results.iter().any(|result| result.status == failure) {
+            if let Some(last) = results.last_mut() {
+                last.status = failure;
+            }
+        }
+    }
+
+    results
+}
+
+fn parse_parts(stdout: &[String]) -> BTreeMap<u8, PrintedPart> {
+    stdout
+        .iter()
+        .filter_map(|line| {
+            // the runner prints an intermediate result first and overwrites it with `\r` once the part finished.
+            let complete = line.contains('\r');
+            let line = strip_ansi(line.rsplit('\r').next()?);
+            let (part, rest) = line.strip_prefix("Part ")?.split_once(": ")?;
+            let part = part.parse::<u8>().ok()?;
+
+            let time = parse_time(&line).map(|(s, nanos)| (s.to_string(), nanos));
+            let answer = match rest.rfind(" (") {
+                Some(idx) if time.is_some() => &rest[..idx],
+                _ => rest,
+            }
+            .trim();
+
+            let answer = match answer {
+                a if a.starts_with('✖') => None,
+                // letter art is printed on the following lines, with the recognized letters after `▼`.
+                a if a.starts_with('▼') => Some(a.trim_start_matches('▼').trim().to_string()),
+                a => Some(a.to_string()),
+            };
+
+            Some((
+                part,
+                PrintedPart {
+                    answer,
+                    time,
+                    complete,
+                },
+            ))
+        })
+        .collect()
+}
+
+fn strip_ansi(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        if c == '\x1b' {
+            chars.by_ref().find(|c| c.is_ascii_alphabetic());
+        } else {
+            out.push(c);
+        }
+    }
+    out
+}
+
+/// The message of a panic, e.g. `attempt to subtract with overflow (src/days/day09.rs:30:5)`.
+fn panic_message(stderr: &[String]) -> String {
+    let Some(index) = stderr.iter().position(|l| l.contains(" panicked at ")) else {
+        return stderr.last().cloned().unwrap_or_default();
+    };
+
+    let location = stderr[index]
+        .split(" panicked at ")
+        .nth(1)
+        .unwrap_or_default()
+        .trim_end_matches(':');
+    match stderr.get(index + 1) {
+        Some(message) => format!("{message} ({location})"),
+        None => location.to_string(),
+    }
+}
+
+/// The first compiler error, e.g. `error[E0425]: cannot find value `x` in this scope`.
+fn build_error(stderr: &[String]) -> String {
+    stderr
+        .iter()
+        .find(|l| l.starts_with("error"))
+        .or_else(|| stderr.first())
+        .cloned()
+        .unwrap_or_default()
+}
+
+impl Report {
+    /// Whether any part failed, see [`Status::is_failure`].
+    pub fn is_failure(&self) -> bool {
+        self.results.iter().any(|r| r.status.is_failure())
+    }
+
+    fn count(&self, f: impl Fn(&Status) -> bool) -> usize {
+        self.results.iter().filter(|r| f(&r.status)).count()
+    }
+
+    /// One line like `20 passed, 2 not implemented, 1 failed`.
+    pub fn summary(&self) -> String {
+        let passed = self.count(|s| *s == Status::Passed);
+        let not_implemented = self.count(|s| *s == Status::NotImplemented);
+        let failed = self.count(Status::is_failure);
+        format!("{passed} passed, {not_implemented} not implemented, {failed} failed")
+    }
+
+    /// Writes the report as JUnit XML, with one test case per part.
+    pub fn to_junit(&self) -> String {
+        let failures = self.count(|s| matches!(s, Status::WrongAnswer { .. }));
+        let errors = self.count(Status::is_failure) - failures;
+        let skipped = self.count(|s| *s == Status::NotImplemented);
+        let total_secs = self.results.iter().filter_map(|r| r.nanos).sum::<f64>() / 1e9;
+
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        xml.push_str(&format!(
+            "<testsuites tests=\"{0}\" failures=\"{failures}\" errors=\"{errors}\" skipped=\"{skipped}\" time=\"{total_secs}\">\n  <testsuite name=\"advent_of_code\" tests=\"{0}\" failures=\"{failures}\" errors=\"{errors}\" skipped=\"{skipped}\" time=\"{total_secs}\">\n",
+            self.results.len()
+        ));
+
+        for result in &self.results {
+            let secs = result.nanos.unwrap_or_default() / 1e9;
+            let open = format!(
+                "    <testcase classname=\"day{}\" name=\"part {}\" time=\"{secs}\"",
+                result.day, result.part
+            );
+            let message = escape_xml(&result.status.to_string());
+            let name = result.status.name();
+            match &result.status {
+                Status::Passed => xml.push_str(&format!("{open}/>\n")),
+                Status::NotImplemented => xml.push_str(&format!(
+                    "{open}>\n      <skipped message=\"{message}\"/>\n    </testcase>\n"
+                )),
+                Status::WrongAnswer { .. } => xml.push_str(&format!(
+                    "{open}>\n      <failure type=\"{name}\" message=\"{message}\"/>\n    </testcase>\n"
+                )),
+                _ => xml.push_str(&format!(
+                    "{open}>\n      <error type=\"{name}\" message=\"{message}\"/>\n    </testcase>\n"
+                )),
+            }
+        }
+
+        xml.push_str("  </testsuite>\n</testsuites>\n");
+        xml
+    }
+
+    /// Writes the report in the Test Anything Protocol, with one test per part.
+    pub fn to_tap(&self) -> String {
+        let mut tap = format!("TAP version 13\n1..{}\n", self.results.len());
+        for (index, result) in self.results.iter().enumerate() {
+            let ok = if result.status.is_failure() { "not ok" } else { "ok" };
+            let directive = match &result.status {
+                Status::Passed => String::new(),
+                Status::NotImplemented => " # SKIP not implemented".into(),
+                status => format!(" - {}", status.to_string().replace('#', "\\#")),
+            };
+            tap.push_str(&format!(
+                "{ok} {} - day {} part {}{directive}\n",
+                index + 1,
+                result.day,
+                result.part
+            ));
+        }
+        tap
+    }
+
+    pub fn write_junit(&self, path: &Path) -> Result<(), io::Error> {
+        fs::write(path, self.to_junit())
+    }
+
+    pub fn write_tap(&self, path: &Path) -> Result<(), io::Error> {
+        fs::write(path, self.to_tap())
+    }
+}
+
+fn escape_xml(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\n', "&#10;")
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{classify, PartResult, Report, Status};
+    use crate::{day, template::run_multi::child_commands::Execution};
+
+    fn lines(s: &[&str]) -> Vec<String> {
+        s.iter().map(ToString::to_string).collect()
+    }
+
+    fn finished(stdout: &[&str], stderr: &[&str], success: bool) -> Execution {
+        Execution::Finished {
+            stdout: lines(stdout),
+            stderr: lines(stderr),
+            success,
+        }
+    }
+
+    fn statuses(results: &[PartResult]) -> Vec<Status> {
+        results.iter().map(|r| r.status.clone()).collect()
+    }
+
+    #[test]
+    fn classifies_finished_runs() {
+        let execution = finished(
+            &[
+                "Part 1: \x1b[1m42\x1b[0m > benching\rPart 1: \x1b[1m42\x1b[0m (1.5µs @ 10 samples)",
+                "Part 2: ✖\rPart 2: ✖             ",
+            ],
+            &[],
+            true,
+        );
+        let results = classify(day!(1), &execution, &[]);
+        assert_eq!(
+            statuses(&results),
+            vec![Status::Passed, Status::NotImplemented]
+        );
+        assert_eq!(results[0].time.as_deref(), Some("1.5µs"));
+        assert_eq!(results[0].nanos, Some(1500.0));
+
+        let results = classify(day!(1), &execution, &["41".into()]);
+        assert_eq!(
+            results[0].status,
+            Status::WrongAnswer {
+                expected: "41".into(),
+                actual: "42".into()
+            }
+        );
+
+        let execution = finished(&["\rPart 1: \x1b[1m42\x1b[0m (3.0ns)"], &[], true);
+        assert_eq!(classify(day!(1), &execution, &[]).len(), 1);
+    }
+
+    #[test]
+    fn classifies_failed_runs() {
+        let execution = finished(
+            &["\rPart 1: \x1b[1m42\x1b[0m (3.0ns)"],
+            &[
+                "thread 'main' panicked at src/days/day01.rs:30:5:",
+                "attempt to subtract with overflow",
+            ],
+            false,
+        );
+        assert_eq!(
+            statuses(&classify(day!(1), &execution, &[])),
+            vec![
+                Status::Passed,
+                Status::Panicked(
+                    "attempt to subtract with overflow (src/days/day01.rs:30:5)".into()
+                )
+            ]
+        );
+
+        let execution = Execution::BuildFailed {
+            stderr: lines(&["   Compiling advent_of_code", "error[E0425]: cannot find value `x`"]),
+        };
+        assert_eq!(
+            classify(day!(1), &execution, &[])[1].status,
+            Status::BuildError("error[E0425]: cannot find value `x`".into())
+        );
+
+        let execution = Execution::TimedOut {
+            stdout: lines(&["\rPart 1: \x1b[1m42\x1b[0m (3.0ns)", "Part 2: 7 > benching"]),
+            stderr: vec![],
+        };
+        assert_eq!(
+            statuses(&classify(day!(1), &execution, &[])),
+            vec![Status::Passed, Status::TimedOut]
+        );
+
+        assert!(classify(day!(1), &Execution::NotScaffolded, &[]).is_empty());
+    }
+
+    #[test]
+    fn classifies_runs_failing_after_all_parts() {
+        let execution = finished(
+            &[
+                "\rPart 1: \x1b[1m42\x1b[0m (3.0ns)",
+                "\rPart 2: \x1b[1m7\x1b[0m (5.0ns)",
+            ],
+            &["thread 'main' panicked at src/days/day01.rs:40:9:", "dropped"],
+            false,
+        );
+        assert_eq!(
+            statuses(&classify(day!(1), &execution, &[])),
+            vec![
+                Status::Passed,
+                Status::Panicked("dropped (src/days/day01.rs:40:9)".into())
+            ]
+        );
+    }
+
+    #[test]
+    fn writes_reports() {
+        let result = |part, status| PartResult {
+            day: day!(1),
+            part,
+            status,
+            time: None,
+            nanos: Some(1000.0),
+        };
+        let report = Report {
+            results: vec![
+                result(1, Status::Passed),
+                result(
+                    2,
+                    Status::WrongAnswer {
+                        expected: "<1>".into(),
+                        actual: "2".into(),
+                    },
+                ),
+            ],
+        };
+
+        assert!(report.is_failure());
+        assert_eq!(report.summary(), "1 passed, 0 not implemented, 1 failed");
+
+        let junit = report.to_junit();
+        assert!(junit.contains("<testsuites tests=\"2\" failures=\"1\" errors=\"0\" skipped=\"0\""));
+        assert!(junit.contains("<testcase classname=\"day01\" name=\"part 1\" time=\"0.000001\"/>"));
+        assert!(junit.contains(
+            "<failure type=\"wrong answer\" message=\"wrong answer: expected &lt;1&gt;, got 2\"/>"
+        ));
+
+        assert_eq!(
+            report.to_tap(),
+            "TAP version 13\n1..2\nok 1 - day 01 part 1\nnot ok 2 - day 01 part 2 - wrong answer: expected <1>, got 2\n"
+        );
+    }
+}
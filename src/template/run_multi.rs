@@ -1,14 +1,27 @@
 use std::{collections::HashSet, io};
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{
+    build::BuildProfile,
+    manifest,
+    report::{self, Report},
+    Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET,
+};
 
 use super::{
     all_days,
     timings::{Timing, Timings},
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
+use child_commands::Execution;
+
+/// Runs the given days and classifies their parts, see [`report::classify`].
+pub fn run_multi(
+    days_to_run: &HashSet<Day>,
+    profile: &BuildProfile,
+    is_timed: bool,
+) -> (Report, Option<Timings>) {
     let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
+    let mut report = Report::default();
 
     let mut need_space = false;
 
@@ -24,25 +37,38 @@ pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -
             println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
             println!("------");
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+            manifest::warn_if_modified(day);
+
+            let execution = child_commands::run_solution(day, is_timed, profile).unwrap();
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
+            match &execution {
+                Execution::NotScaffolded => println!("Not solved."),
+                Execution::BuildFailed { .. } => {}
+                Execution::Finished { stdout, .. } | Execution::TimedOut { stdout, .. } => {
+                    timings.push(child_commands::parse_exec_time(stdout, day));
+                }
             }
+
+            let results = report::classify(day, &execution, &report::expected_answers(day));
+            for result in results.iter().filter(|r| r.status.is_failure()) {
+                println!("✖ Part {}: {}", result.part, result.status);
+            }
+            report.results.extend(results);
         });
 
+    if !report.results.is_empty() {
+        println!("\n{ANSI_BOLD}Results:{ANSI_RESET} {}", report.summary());
+    }
+
     if is_timed {
         let timings = Timings { data: timings };
         let total_millis = timings.total_millis();
         println!(
-            "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
+            "{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
         );
-        Some(timings)
+        (report, Some(timings))
     } else {
-        None
+        (report, None)
     }
 }
 
@@ -68,26 +94,63 @@ pub fn get_path_for_bin(day: Day) -> String {
 /// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
 pub mod child_commands {
     use super::{get_path_for_bin, Error};
-    use crate::template::Day;
+    use crate::template::{alloc::AllocStats, build::BuildProfile, config, rusage::Usage, Day};
     use std::{
-        io::{BufRead, BufReader},
+        io::{BufRead, BufReader, Read},
         path::Path,
-        process::{Command, Stdio},
+        process::{Child, Command, ExitStatus, Stdio},
+        sync::{Arc, Mutex},
         thread,
+        time::{Duration, Instant},
     };
 
+    /// What happened when running the solution bin of a day.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub enum Execution {
+        /// The day has not been scaffolded yet.
+        NotScaffolded,
+        BuildFailed {
+            stderr: Vec<String>,
+        },
+        Finished {
+            stdout: Vec<String>,
+            stderr: Vec<String>,
+            success: bool,
+        },
+        /// The solution ran longer than `run.timeout_secs` and was stopped.
+        TimedOut {
+            stdout: Vec<String>,
+            stderr: Vec<String>,
+        },
+    }
+
     /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
+    pub fn run_solution(
+        day: Day,
+        is_timed: bool,
+        profile: &BuildProfile,
+    ) -> Result<Execution, Error> {
         // skip command invocation for days that have not been scaffolded yet.
         if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+            return Ok(Execution::NotScaffolded);
         }
 
         let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+        let mut args = vec!["--quiet", "--bin", &day_padded];
 
-        if is_release {
-            args.push("--release");
+        // build separately, so compile errors are told apart from panics and do not count towards the timeout.
+        let mut build = Command::new("cargo");
+        build
+            .arg("build")
+            .args(&args)
+            .stdout(Stdio::null())
+            .stderr(Stdio::piped());
+        profile.configure(&mut build);
+        let build = collect(build.spawn()?, None)?;
+        if !build.status.is_some_and(|s| s.success()) {
+            return Ok(Execution::BuildFailed {
+                stderr: build.stderr,
+            });
         }
 
         if is_timed {
@@ -97,42 +160,125 @@ pub mod child_commands {
         }
 
         // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
-
-        let mut cmd = Command::new("cargo")
-            .args(&args)
+        // forward output to stdout/stderr while grabbing the lines.
+        let mut cmd = Command::new("cargo");
+        cmd.arg("run")
             .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()?;
+            .stderr(Stdio::piped());
+        profile.configure(&mut cmd);
+        cmd.args(&args);
+
+        // run in a process group of its own, so the solution can be stopped along with cargo.
+        #[cfg(unix)]
+        std::os::unix::process::CommandExt::process_group(&mut cmd, 0);
+
+        let timeout = config::get().timeout_secs.map(Duration::from_secs);
+        let Collected {
+            stdout,
+            stderr,
+            status,
+        } = collect(cmd.spawn()?, timeout)?;
+
+        Ok(match status {
+            Some(status) => Execution::Finished {
+                stdout,
+                stderr,
+                success: status.success(),
+            },
+            None => {
+                eprintln!();
+                eprintln!(
+                    "Stopped after {}s, see `run.timeout_secs`.",
+                    timeout.unwrap_or_default().as_secs()
+                );
+                Execution::TimedOut { stdout, stderr }
+            }
+        })
+    }
 
-        let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
-        let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
+    /// Output of a child, with `None` as status if it was stopped.
+    struct Collected {
+        stdout: Vec<String>,
+        stderr: Vec<String>,
+        status: Option<ExitStatus>,
+    }
 
-        let mut output = vec![];
+    /// Forwards and collects the output of a child, stopping it after `timeout`.
+    fn collect(mut child: Child, timeout: Option<Duration>) -> Result<Collected, Error> {
+        let stdout = forward(child.stdout.take(), |line| println!("{line}"));
+        let stderr = forward(child.stderr.take(), |line| eprintln!("{line}"));
 
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
+        let deadline = timeout.map(|timeout| Instant::now() + timeout);
+        let status = loop {
+            if let Some(status) = child.try_wait()? {
+                break Some(status);
+            }
+            if deadline.is_some_and(|deadline| Instant::now() >= deadline) {
+                kill(&mut child);
+                break None;
+            }
+            thread::sleep(Duration::from_millis(10));
+        };
+
+        // the readers are not joined after a timeout, as stopped solutions may leave the pipes open.
+        let lines = |(lines, reader): (Arc<Mutex<Vec<String>>>, thread::JoinHandle<()>)| {
+            if status.is_some() {
+                let _ = reader.join();
+            }
+            let lines = lines.lock().map(|l| l.clone()).unwrap_or_default();
+            lines
+        };
+
+        Ok(Collected {
+            stdout: lines(stdout),
+            stderr: lines(stderr),
+            status,
+        })
+    }
+
+    fn forward(
+        pipe: Option<impl Read + Send + 'static>,
+        print: impl Fn(&str) + Send + 'static,
+    ) -> (Arc<Mutex<Vec<String>>>, thread::JoinHandle<()>) {
+        let lines = Arc::new(Mutex::new(vec![]));
+        let collected = Arc::clone(&lines);
+
+        let reader = thread::spawn(move || {
+            let Some(pipe) = pipe else {
+                return;
+            };
+            for line in BufReader::new(pipe).lines().map_while(Result::ok) {
+                print(&line);
+                if let Ok(mut lines) = collected.lock() {
+                    lines.push(line);
+                }
+            }
         });
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
-        }
+        (lines, reader)
+    }
 
-        thread.join().unwrap();
-        cmd.wait()?;
+    fn kill(child: &mut Child) {
+        // `cargo run` does not pass the signal on to the solution, so the whole group is stopped.
+        #[cfg(unix)]
+        let _ = Command::new("kill")
+            .args(["-s", "KILL", "--", &format!("-{}", child.id())])
+            .status();
 
-        Ok(output)
+        let _ = child.kill();
+        let _ = child.wait();
     }
 
     pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
         let mut timings = super::Timing {
             day,
+            parse: None,
             part_1: None,
             part_2: None,
+            part_1_allocs: None,
+            part_2_allocs: None,
+            part_1_usage: None,
+            part_2_usage: None,
             total_nanos: 0_f64,
         };
 
@@ -149,13 +295,20 @@ pub mod child_commands {
                 };
 
                 let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
+                let (rest, usage) = split_usage(l);
+                Some((part, timing_str, nanos, split_allocs(rest).1, usage))
             })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
+            .for_each(|(part, timing_str, nanos, allocs, usage)| {
+                if part.contains("Parse") {
+                    timings.parse = Some(timing_str.into());
+                } else if part.contains("Part 1") {
                     timings.part_1 = Some(timing_str.into());
+                    timings.part_1_allocs = allocs;
+                    timings.part_1_usage = usage;
                 } else if part.contains("Part 2") {
                     timings.part_2 = Some(timing_str.into());
+                    timings.part_2_allocs = allocs;
+                    timings.part_2_usage = usage;
                 }
 
                 timings.total_nanos += nanos;
@@ -164,20 +317,42 @@ pub mod child_commands {
         timings
     }
 
+    /// Splits the allocations printed with the `alloc-stats` feature off a line, e.g. `[12 allocs, 1.5 KiB, peak 100 B]`.
+    fn split_allocs(line: &str) -> (&str, Option<AllocStats>) {
+        line.trim_end()
+            .strip_suffix(']')
+            .and_then(|l| l.rsplit_once(" ["))
+            .and_then(|(rest, allocs)| Some((rest, Some(AllocStats::parse(allocs)?))))
+            .unwrap_or((line, None))
+    }
+
+    /// Splits the CPU time and peak RSS measured on Linux off a line, e.g. `{3.1ms user, 400.0µs sys, 2.90× cpu, rss 12.3 MiB}`.
+    fn split_usage(line: &str) -> (&str, Option<Usage>) {
+        line.trim_end()
+            .strip_suffix('}')
+            .and_then(|l| l.rsplit_once(" {"))
+            .and_then(|(rest, usage)| Some((rest, Some(Usage::parse(usage)?))))
+            .unwrap_or((line, None))
+    }
+
     fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
         s.split(postfix).next()?.parse().ok()
     }
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
+    /// Parses the time of a part line, e.g. `12.0µs` from `Part 1: 42 (12.0µs @ 10 samples)` or `Part 1: 42 (12.0µs)`.
+    pub fn parse_time(line: &str) -> Option<(&str, f64)> {
+        let line = split_allocs(split_usage(line).0).0;
+
         // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
         let str_timing = line
             .split(" samples)")
             .next()?
             .split('(')
-            .last()?
+            .next_back()?
             .split('@')
             .next()?
-            .trim();
+            .trim()
+            .trim_end_matches(')');
 
         let parsed_timing = match str_timing {
             s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
@@ -205,7 +380,7 @@ pub mod child_commands {
 
     #[cfg(feature = "test_lib")]
     mod tests {
-        use super::parse_exec_time;
+        use super::{parse_exec_time, parse_time};
 
         use crate::day;
 
@@ -239,6 +414,59 @@ pub mod child_commands {
             assert_eq!(res.part_2.unwrap(), "100ms");
         }
 
+        #[test]
+        fn parses_parse_times() {
+            let res = parse_exec_time(
+                &[
+                    "Parse: ✔ (1.5ms @ 10 samples)".into(),
+                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
+                    "Part 2: ✖        ".into(),
+                ],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos, 1500074.13_f64);
+            assert_eq!(res.parse.unwrap(), "1.5ms");
+            assert_eq!(res.part_1.unwrap(), "74.13ns");
+            assert_eq!(res.part_2.is_none(), true);
+        }
+
+        #[test]
+        fn parses_allocations() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 0 (74.13ns @ 100000 samples) [12 allocs, 1.5 KiB, peak 100 B]".into(),
+                    "Part 2: [1, 2] (2.0ms @ 10 samples)".into(),
+                ],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos, 2000074.13_f64);
+            assert_eq!(res.part_1.unwrap(), "74.13ns");
+            assert_eq!(res.part_1_allocs.unwrap().allocations, 12);
+            assert_eq!(res.part_2.unwrap(), "2.0ms");
+            assert_eq!(res.part_2_allocs, None);
+            assert_eq!(
+                parse_time("Part 1: 42 (3.0ns) [1 allocs, 8 B, peak 8 B]"),
+                Some(("3.0ns", 3.0))
+            );
+        }
+
+        #[test]
+        fn parses_resource_usage() {
+            let res = parse_exec_time(
+                &[
+                    "Part 1: 0 (1.0ms @ 10 samples) [12 allocs, 1.5 KiB, peak 100 B] {3.8ms user, 200.0µs sys, 4.00× cpu, rss 2.0 MiB}".into(),
+                    "Part 2: 10 (2.0ms @ 10 samples) {1.9ms user, 0.0ns sys, 0.95× cpu, rss 1.0 MiB}".into(),
+                ],
+                day!(1),
+            );
+            assert_approx_eq!(res.total_nanos, 3000000_f64);
+            assert_eq!(res.part_1_allocs.unwrap().allocations, 12);
+            assert_eq!(res.part_1_usage.unwrap().cpu_ratio, 4.0);
+            assert_eq!(res.part_2.unwrap(), "2.0ms");
+            assert_eq!(res.part_2_allocs, None);
+            assert_eq!(res.part_2_usage.unwrap().peak_rss, 1024 * 1024);
+        }
+
         #[test]
         fn parses_missing_parts() {
             let res = parse_exec_time(
@@ -7,59 +7,141 @@ use std::time::{Duration, Instant};
 use std::{cmp, env, process};
 
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::alloc::{self, AllocStats};
+use crate::template::rusage::{Meter, Usage};
+use crate::template::{aoc_cli, config, glyphs, trace, Day, ANSI_ITALIC, ANSI_RESET};
 
 pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    let (result, duration, samples, allocs, usage) = {
+        let _span = crate::aoc_span!("part {part}");
+        run_timed(func, input, day, |result| {
+            print_result(result, &part_str, "")
+        })
+    };
+    trace::flush();
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    let stats = format_duration(&duration, samples) + &format_allocs(allocs) + &format_usage(usage);
+    print_result(&result, &part_str, &stats);
 
     if let Some(result) = result {
         submit_result(result, day, part);
     }
 }
 
+/// Run the shared `parse` function of a solution and return its output for the parts.
+/// Timed like a part, see [`run_timed`].
+pub fn run_parse<I: Clone, T>(func: impl Fn(I) -> T, input: I, day: Day) -> T {
+    let (parsed, duration, samples, allocs, usage) = {
+        let _span = crate::aoc_span!("parse");
+        run_timed(func, input, day, |_| print!("Parse: ✔"))
+    };
+    trace::flush();
+
+    print!("\r");
+    println!(
+        "Parse: ✔{}{}{}",
+        format_duration(&duration, samples),
+        format_allocs(allocs),
+        format_usage(usage)
+    );
+
+    parsed
+}
+
+/// The answer and timings of a part solved in-process, see [`solutions!`](crate::solutions).
+#[derive(Clone, Debug)]
+pub struct Solved {
+    pub result: Option<String>,
+    /// Time spent in the shared `parse` function, if the solution has one.
+    pub parse: Option<Duration>,
+    pub duration: Duration,
+}
+
+impl Solved {
+    /// The answer as text, with block letters recognized if possible.
+    pub fn answer(&self) -> Option<String> {
+        self.result.as_ref().map(|result| {
+            if glyphs::is_letter_art(result) {
+                glyphs::recognize(result).unwrap_or_else(|_| result.clone())
+            } else {
+                result.clone()
+            }
+        })
+    }
+}
+
+/// Run a solution part once without printing anything. `part` has to be 1 or 2.
+pub fn solve_once<I: ?Sized, A: Display, B: Display>(
+    part_one: impl Fn(&I) -> Option<A>,
+    part_two: impl Fn(&I) -> Option<B>,
+    input: &I,
+    part: u8,
+) -> (Option<String>, Duration) {
+    let timer = Instant::now();
+    let result = if part == 1 {
+        part_one(input).map(|r| r.to_string())
+    } else {
+        part_two(input).map(|r| r.to_string())
+    };
+    (result, timer.elapsed())
+}
+
 /// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
 ///  1. in debug, the function is executed once.
 ///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+///     The budget can be configured in `aoc.toml`.
+///
+/// With the `alloc-stats` feature, the allocations of the first execution are counted.
+/// On Linux, the CPU time and peak RSS of all executions are measured, see [`Meter`].
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
+    day: Day,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+) -> (T, Duration, u128, Option<AllocStats>, Option<Usage>) {
+    let input_clone = input.clone();
+    let meter = Meter::start();
+    let counter = alloc::ENABLED.then(alloc::Counter::start);
     let timer = Instant::now();
     let result = {
-        let input = input.clone();
-
         #[cfg(feature = "dhat-heap")]
         let _profiler = dhat::Profiler::new_heap();
 
-        func(input)
+        func(input_clone)
     };
     let base_time = timer.elapsed();
+    let allocs = counter.map(|counter| counter.stop());
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    let (run, runs) = if std::env::args().any(|x| x == "--time") {
+        let run = bench(func, input, day, &base_time);
+        (run, run.1 + 1)
     } else {
-        (base_time, 1)
+        ((base_time, 1), 1)
     };
+    let usage = meter.and_then(|meter| meter.stop(runs, timer.elapsed()));
 
-    (result, run.0, run.1)
+    (result, run.0, run.1, allocs, usage)
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
+fn bench<I: Clone, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    day: Day,
+    base_time: &Duration,
+) -> (Duration, u128) {
     let mut stdout = stdout();
+    let budget = config::get().benchmark_for(day);
 
     print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
     let _ = stdout.flush();
 
-    let bench_iterations =
-        (Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10)).clamp(10, 10000);
+    let bench_iterations = (Duration::from_millis(budget.budget_ms).as_nanos()
+        / cmp::max(base_time.as_nanos(), 10))
+    .clamp(budget.min_samples, budget.max_samples);
 
     let mut timers: Vec<Duration> = vec![];
 
@@ -86,6 +168,14 @@ fn average_duration(numbers: &[Duration]) -> u128 {
         / numbers.len() as u128
 }
 
+fn format_allocs(allocs: Option<AllocStats>) -> String {
+    allocs.map(|allocs| format!(" [{allocs}]")).unwrap_or_default()
+}
+
+fn format_usage(usage: Option<Usage>) -> String {
+    usage.map(|usage| format!(" {{{usage}}}")).unwrap_or_default()
+}
+
 fn format_duration(duration: &Duration, samples: u128) -> String {
     if samples == 1 {
         format!(" ({duration:.1?})")
@@ -99,8 +189,16 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
 
     match result {
         Some(result) => {
-            if result.to_string().contains('\n') {
-                let str = format!("{part}: ▼ {duration_str}");
+            let result = result.to_string();
+            if result.contains('\n') {
+                let letters = if glyphs::is_letter_art(&result) {
+                    glyphs::recognize(&result)
+                        .map(|l| format!("{ANSI_BOLD}{l}{ANSI_RESET}"))
+                        .unwrap_or_default()
+                } else {
+                    String::new()
+                };
+                let str = format!("{part}: ▼ {letters}{duration_str}");
                 if is_intermediate_result {
                     print!("{str}");
                 } else {
@@ -164,6 +262,22 @@ fn submit_result<T: Display>(
         process::exit(1);
     }
 
+    let mut result = result.to_string();
+
+    // answers drawn as block letters have to be submitted as text.
+    if glyphs::is_letter_art(&result) {
+        match glyphs::recognize(&result) {
+            Ok(letters) => {
+                println!("Recognized letters \"{letters}\".");
+                result = letters;
+            }
+            Err(e) => {
+                eprintln!("Could not recognize the letters of the result: {e}");
+                process::exit(1);
+            }
+        }
+    }
+
     println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    Some(aoc_cli::submit(day, part, &result))
 }
@@ -0,0 +1,228 @@
+/// CPU time and peak memory of solution runs, read with `getrusage` on Linux.
+/// Shows how much CPU time parallel days burn next to their wall-clock time.
+use std::{fmt::Display, time::Duration};
+
+use crate::template::alloc::{format_bytes, parse_bytes};
+
+/// Resources used by the runs of a part, see [`Meter`].
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Usage {
+    /// CPU time spent in user mode, per run like the timing.
+    pub user: Duration,
+    /// CPU time spent in the kernel, per run like the timing.
+    pub system: Duration,
+    /// CPU time divided by wall-clock time, e.g. close to 4 for a part that keeps 4 threads busy.
+    pub cpu_ratio: f64,
+    /// The most memory resident at the same time while the part ran, in bytes.
+    pub peak_rss: u64,
+}
+
+impl Display for Usage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{:.1?} user, {:.1?} sys, {:.2}× cpu, rss {}",
+            self.user,
+            self.system,
+            self.cpu_ratio,
+            format_bytes(self.peak_rss)
+        )
+    }
+}
+
+impl Usage {
+    /// Parses the output of [`Display`], e.g. `3.1ms user, 400.0µs sys, 2.90× cpu, rss 12.3 MiB`.
+    /// The values are rounded like in the output.
+    pub fn parse(s: &str) -> Option<Self> {
+        let mut fields = s.split(", ");
+        let user = parse_duration(fields.next()?.strip_suffix(" user")?)?;
+        let system = parse_duration(fields.next()?.strip_suffix(" sys")?)?;
+        let cpu_ratio = fields.next()?.strip_suffix("× cpu")?.parse().ok()?;
+        let peak_rss = parse_bytes(fields.next()?.strip_prefix("rss ")?)?;
+        Some(Usage {
+            user,
+            system,
+            cpu_ratio,
+            peak_rss,
+        })
+    }
+}
+
+/// Measures the resources used from its creation on.
+pub struct Meter {
+    start: sys::Snapshot,
+}
+
+impl Meter {
+    /// Starts measuring and resets the peak RSS of the process, so the peak belongs to this part.
+    /// Resetting needs Linux 4.0, on older kernels the peak of the whole process is reported.
+    /// `None` on platforms other than Linux.
+    pub fn start() -> Option<Self> {
+        sys::reset_peak_rss();
+        Some(Meter {
+            start: sys::Snapshot::take()?,
+        })
+    }
+
+    /// Stops measuring after `runs` runs, that took `wall` in total.
+    pub fn stop(&self, runs: u128, wall: Duration) -> Option<Usage> {
+        let end = sys::Snapshot::take()?;
+        let runs = u32::try_from(runs.max(1)).unwrap_or(u32::MAX);
+
+        let user = end.user.saturating_sub(self.start.user);
+        let system = end.system.saturating_sub(self.start.system);
+        let cpu_ratio = if wall.is_zero() {
+            0.0
+        } else {
+            (user + system).as_secs_f64() / wall.as_secs_f64()
+        };
+
+        Some(Usage {
+            user: user / runs,
+            system: system / runs,
+            cpu_ratio,
+            peak_rss: end.max_rss,
+        })
+    }
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn parse_duration(s: &str) -> Option<Duration> {
+    // for possible formats, see `Debug` of `Duration`.
+    let (value, nanos) = if let Some(value) = s.strip_suffix("ns") {
+        (value, 1.0)
+    } else if let Some(value) = s.strip_suffix("µs") {
+        (value, 1e3)
+    } else if let Some(value) = s.strip_suffix("ms") {
+        (value, 1e6)
+    } else {
+        (s.strip_suffix('s')?, 1e9)
+    };
+    let value: f64 = value.parse().ok()?;
+    Some(Duration::from_nanos((value * nanos).round() as u64))
+}
+
+#[cfg(target_os = "linux")]
+mod sys {
+    use std::{
+        fs, mem,
+        os::raw::{c_int, c_long},
+        time::Duration,
+    };
+
+    const RUSAGE_SELF: c_int = 0;
+
+    #[repr(C)]
+    struct Timeval {
+        tv_sec: c_long,
+        tv_usec: c_long,
+    }
+
+    #[repr(C)]
+    struct Rusage {
+        ru_utime: Timeval,
+        ru_stime: Timeval,
+        ru_maxrss: c_long,
+        // the other fields are not used.
+        _rest: [c_long; 13],
+    }
+
+    extern "C" {
+        fn getrusage(who: c_int, usage: *mut Rusage) -> c_int;
+    }
+
+    /// CPU time of all threads of the process so far, and its peak RSS.
+    pub struct Snapshot {
+        pub user: Duration,
+        pub system: Duration,
+        pub max_rss: u64,
+    }
+
+    impl Snapshot {
+        pub fn take() -> Option<Self> {
+            // SAFETY: `Rusage` matches `struct rusage` and is plain data, so zeroed memory is valid.
+            let mut usage: Rusage = unsafe { mem::zeroed() };
+            // SAFETY: `usage` is a valid pointer to a `struct rusage` for the call.
+            if unsafe { getrusage(RUSAGE_SELF, &mut usage) } != 0 {
+                return None;
+            }
+
+            Some(Snapshot {
+                user: to_duration(&usage.ru_utime),
+                system: to_duration(&usage.ru_stime),
+                // Linux reports kilobytes.
+                max_rss: u64::try_from(usage.ru_maxrss).ok()? * 1024,
+            })
+        }
+    }
+
+    fn to_duration(time: &Timeval) -> Duration {
+        let secs = u64::try_from(time.tv_sec).unwrap_or_default();
+        let micros = u64::try_from(time.tv_usec).unwrap_or_default();
+        Duration::from_secs(secs) + Duration::from_micros(micros)
+    }
+
+    /// Resets the peak RSS to the current RSS, see `man 5 proc` on `/proc/pid/clear_refs`.
+    pub fn reset_peak_rss() {
+        let _ = fs::write("/proc/self/clear_refs", "5");
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+mod sys {
+    use std::time::Duration;
+
+    pub struct Snapshot {
+        pub user: Duration,
+        pub system: Duration,
+        pub max_rss: u64,
+    }
+
+    impl Snapshot {
+        pub fn take() -> Option<Self> {
+            None
+        }
+    }
+
+    pub fn reset_peak_rss() {}
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use std::time::Duration;
+
+    use super::{Meter, Usage};
+
+    #[test]
+    fn parses_usage() {
+        let usage = Usage {
+            user: Duration::from_micros(3100),
+            system: Duration::from_micros(400),
+            cpu_ratio: 2.9,
+            peak_rss: 1536,
+        };
+        assert_eq!(
+            usage.to_string(),
+            "3.1ms user, 400.0µs sys, 2.90× cpu, rss 1.5 KiB"
+        );
+        assert_eq!(Usage::parse(&usage.to_string()), Some(usage));
+        assert_eq!(Usage::parse("3.1ms user"), None);
+    }
+
+    #[test]
+    #[cfg(target_os = "linux")]
+    fn measures_cpu_time() {
+        let meter = Meter::start().unwrap();
+        let timer = std::time::Instant::now();
+        let mut x = 0_u64;
+        while timer.elapsed() < Duration::from_millis(20) {
+            x = std::hint::black_box(x.wrapping_add(1));
+        }
+
+        let usage = meter.stop(2, timer.elapsed()).unwrap();
+        assert!(usage.user + usage.system > Duration::ZERO);
+        assert!(usage.peak_rss > 0);
+    }
+}
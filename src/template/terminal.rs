@@ -0,0 +1,97 @@
+/// Helpers for writing long output to the terminal.
+use std::{
+    env,
+    io::{stdout, IsTerminal, Write},
+    process::{Command, Stdio},
+};
+
+use crate::template::{ANSI_BOLD, ANSI_RESET};
+
+const DEFAULT_WIDTH: usize = 80;
+const DEFAULT_HEIGHT: usize = 24;
+
+/// Returns `true` if stdout is an interactive terminal, i.e. ANSI styles can be used.
+pub fn is_interactive() -> bool {
+    stdout().is_terminal()
+}
+
+/// Width of the terminal in columns. Honors the `COLUMNS` env variable.
+pub fn width() -> usize {
+    env::var("COLUMNS")
+        .ok()
+        .and_then(|c| c.parse().ok())
+        .or_else(|| stty_size().map(|(_, cols)| cols))
+        .unwrap_or(DEFAULT_WIDTH)
+}
+
+/// Height of the terminal in rows. Honors the `LINES` env variable.
+pub fn height() -> usize {
+    env::var("LINES")
+        .ok()
+        .and_then(|c| c.parse().ok())
+        .or_else(|| stty_size().map(|(rows, _)| rows))
+        .unwrap_or(DEFAULT_HEIGHT)
+}
+
+fn stty_size() -> Option<(usize, usize)> {
+    let output = Command::new("stty")
+        .arg("size")
+        .stdin(Stdio::inherit())
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    let size = String::from_utf8_lossy(&output.stdout);
+    let (rows, cols) = size.trim().split_once(' ')?;
+    Some((rows.parse().ok()?, cols.parse().ok()?))
+}
+
+/// Prints `text`, piping it through `$PAGER` (or `less -R`) if it does not fit on the screen.
+/// Falls back to printing directly if the pager can not be started.
+pub fn page(text: &str) {
+    if is_interactive() && text.lines().count() >= height() && pipe_to_pager(text).is_some() {
+        return;
+    }
+    print!("{text}");
+    let _ = stdout().flush();
+}
+
+fn pipe_to_pager(text: &str) -> Option<()> {
+    let pager = env::var("PAGER").unwrap_or_else(|_| "less -R".into());
+    let mut parts = pager.split_whitespace();
+    let mut child = Command::new(parts.next()?)
+        .args(parts)
+        .stdin(Stdio::piped())
+        .spawn()
+        .ok()?;
+
+    // the pager might exit before reading everything, e.g. when quitting `less` early.
+    let _ = child.stdin.take()?.write_all(text.as_bytes());
+    child.wait().ok()?;
+    Some(())
+}
+
+/// Prints rows as columns aligned to the widest cell, with the first row as bold header.
+pub fn print_table(rows: &[Vec<String>]) {
+    let widths: Vec<usize> = (0..rows[0].len())
+        .map(|col| {
+            rows.iter()
+                .map(|row| row[col].chars().count())
+                .max()
+                .unwrap_or_default()
+        })
+        .collect();
+
+    for (index, row) in rows.iter().enumerate() {
+        let line = row
+            .iter()
+            .zip(&widths)
+            .map(|(cell, width)| format!("{cell:width$}"))
+            .collect::<Vec<_>>()
+            .join("  ");
+        if index == 0 {
+            println!("{ANSI_BOLD}{}{ANSI_RESET}", line.trim_end());
+        } else {
+            println!("{}", line.trim_end());
+        }
+    }
+}
@@ -1,16 +1,24 @@
 use std::{collections::HashMap, fs, io::Error, str::FromStr};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use std::time::Duration;
 
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+use crate::template::{alloc::AllocStats, config, machine::Machine, rusage::Usage, Day};
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
 pub struct Timing {
     pub day: Day,
+    /// Time spent in the shared `parse` function, if the solution has one.
+    pub parse: Option<String>,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
+    /// Allocations of the parts, if the solution was built with the `alloc-stats` feature.
+    pub part_1_allocs: Option<AllocStats>,
+    pub part_2_allocs: Option<AllocStats>,
+    /// CPU time and peak RSS of the parts, measured on Linux.
+    pub part_1_usage: Option<Usage>,
+    pub part_2_usage: Option<Usage>,
     pub total_nanos: f64,
 }
 
@@ -21,22 +29,70 @@ pub struct Timings {
     pub data: Vec<Timing>,
 }
 
-impl Timings {
+/// Benchmark times recorded on one machine profile.
+#[derive(Clone, Debug)]
+pub struct Profile {
+    pub key: String,
+    /// `None` for timings that were stored before machine profiles were recorded.
+    pub machine: Option<Machine>,
+    pub timings: Timings,
+}
+
+/// Represents the contents of the timings file: benchmark times keyed by machine profile.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct StoredTimings {
+    pub profiles: Vec<Profile>,
+}
+
+/// Key of timings that were stored without a machine profile.
+const LEGACY_PROFILE_KEY: &str = "unknown";
+
+impl StoredTimings {
     /// Dehydrate timings to a JSON file.
     pub fn store_file(&self) -> Result<(), Error> {
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
+        let mut file = fs::File::create(&config::get().timings_path)?;
         json.format_to(&mut file)
     }
 
     /// Rehydrate timings from a JSON file. If not present, returns empty timings.
     pub fn read_from_file() -> Self {
-        fs::read_to_string(TIMINGS_FILE_PATH)
+        fs::read_to_string(&config::get().timings_path)
             .map_err(|x| x.to_string())
-            .and_then(Timings::try_from)
+            .and_then(StoredTimings::try_from)
             .unwrap_or_default()
     }
 
+    /// Timings recorded on `machine`'s profile, empty if there are none.
+    pub fn get(&self, machine: &Machine) -> Timings {
+        let key = machine.key();
+        self.profiles
+            .iter()
+            .find(|p| p.key == key)
+            .map(|p| p.timings.clone())
+            .unwrap_or_default()
+    }
+
+    /// Replace the timings of `machine`'s profile.
+    pub fn insert(&mut self, machine: &Machine, timings: Timings) {
+        let profile = Profile {
+            key: machine.key(),
+            machine: Some(machine.clone()),
+            timings,
+        };
+
+        match self.profiles.iter_mut().find(|p| p.key == profile.key) {
+            Some(existing) => *existing = profile,
+            None => {
+                self.profiles.push(profile);
+                self.profiles.sort_unstable_by(|a, b| a.key.cmp(&b.key));
+            }
+        }
+    }
+}
+
+impl Timings {
     /// Merge two sets of timings, overwriting `self` with `other` if present.
     pub fn merge(&self, new: &Self) -> Self {
         let mut data: Vec<Timing> = vec![];
@@ -51,7 +107,7 @@ impl Timings {
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|a| a.day);
         Timings { data }
     }
 
@@ -87,7 +143,14 @@ impl TryFrom<String> for Timings {
 
     fn try_from(value: String) -> Result<Self, Self::Error> {
         let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+        Timings::try_from(&json)
+    }
+}
+
+impl TryFrom<&JsonValue> for Timings {
+    type Error = String;
 
+    fn try_from(json: &JsonValue) -> Result<Self, Self::Error> {
         let json_data = json
             .get::<HashMap<String, JsonValue>>()
             .ok_or("expected JSON document to be an object.")?
@@ -107,6 +170,74 @@ impl TryFrom<String> for Timings {
 
 /* -------------------------------------------------------------------------- */
 
+impl From<StoredTimings> for JsonValue {
+    fn from(value: StoredTimings) -> Self {
+        let profiles = value
+            .profiles
+            .into_iter()
+            .map(|profile| {
+                let mut json = JsonValue::from(profile.timings);
+                if let (Some(machine), JsonValue::Object(map)) = (&profile.machine, &mut json) {
+                    map.insert("machine".into(), JsonValue::from(machine));
+                }
+                (profile.key, json)
+            })
+            .collect();
+
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        map.insert("profiles".into(), JsonValue::Object(profiles));
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for StoredTimings {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let document = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        // timings files without machine profiles only have a top-level `data` key.
+        if !document.contains_key("profiles") {
+            return Ok(StoredTimings {
+                profiles: vec![Profile {
+                    key: LEGACY_PROFILE_KEY.into(),
+                    machine: None,
+                    timings: Timings::try_from(&json)?,
+                }],
+            });
+        }
+
+        let mut profiles = document
+            .get("profiles")
+            .and_then(|v| v.get::<HashMap<String, JsonValue>>())
+            .ok_or("expected `json.profiles` to be an object.")?
+            .iter()
+            .map(|(key, value)| {
+                let machine = value
+                    .get::<HashMap<String, JsonValue>>()
+                    .and_then(|map| map.get("machine"))
+                    .map(Machine::try_from)
+                    .transpose()?;
+
+                Ok(Profile {
+                    key: key.clone(),
+                    machine,
+                    timings: Timings::try_from(value)?,
+                })
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        profiles.sort_unstable_by(|a, b| a.key.cmp(&b.key));
+        Ok(StoredTimings { profiles })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
 impl From<&Timing> for JsonValue {
     fn from(value: &Timing) -> Self {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
@@ -114,6 +245,10 @@ impl From<&Timing> for JsonValue {
         map.insert("day".into(), JsonValue::String(value.day.to_string()));
         map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
 
+        if let Some(parse) = &value.parse {
+            map.insert("parse".into(), JsonValue::String(parse.clone()));
+        }
+
         let part_1 = value.part_1.clone().map(JsonValue::String);
         let part_2 = value.part_2.clone().map(JsonValue::String);
 
@@ -133,6 +268,24 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        for (key, allocs) in [
+            ("part_1_allocs", &value.part_1_allocs),
+            ("part_2_allocs", &value.part_2_allocs),
+        ] {
+            if let Some(allocs) = allocs {
+                map.insert(key.into(), JsonValue::from(allocs));
+            }
+        }
+
+        for (key, usage) in [
+            ("part_1_usage", &value.part_1_usage),
+            ("part_2_usage", &value.part_2_usage),
+        ] {
+            if let Some(usage) = usage {
+                map.insert(key.into(), JsonValue::from(usage));
+            }
+        }
+
         JsonValue::Object(map)
     }
 }
@@ -151,6 +304,9 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|day| Day::from_str(day).ok())
             .ok_or("Expected timing.day to be a Day struct.")?;
 
+        // timings stored before parsing was timed separately have no `parse` key.
+        let parse = json.get("parse").and_then(|v| v.get::<String>());
+
         let part_1 = json
             .get("part_1")
             .map(|v| if v.is_null() { None } else { v.get::<String>() })
@@ -166,15 +322,95 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // only timings of solutions built with the `alloc-stats` feature have allocations.
+        let part_1_allocs = json.get("part_1_allocs").map(AllocStats::try_from).transpose()?;
+        let part_2_allocs = json.get("part_2_allocs").map(AllocStats::try_from).transpose()?;
+
+        // only timings measured on Linux have resource usage.
+        let part_1_usage = json.get("part_1_usage").map(Usage::try_from).transpose()?;
+        let part_2_usage = json.get("part_2_usage").map(Usage::try_from).transpose()?;
+
         Ok(Timing {
             day,
+            parse: parse.cloned(),
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
+            part_1_allocs,
+            part_2_allocs,
+            part_1_usage,
+            part_2_usage,
             total_nanos,
         })
     }
 }
 
+impl From<&AllocStats> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &AllocStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        map.insert("allocations".into(), JsonValue::Number(value.allocations as f64));
+        map.insert("bytes".into(), JsonValue::Number(value.bytes as f64));
+        map.insert("peak".into(), JsonValue::Number(value.peak as f64));
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for AllocStats {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let field = |key: &str| {
+            value
+                .get::<HashMap<String, JsonValue>>()
+                .and_then(|map| map.get(key))
+                .and_then(|v| v.get::<f64>().copied())
+                .map(|v| v as u64)
+                .ok_or(format!("Expected allocations.{key} to be a number."))
+        };
+
+        Ok(AllocStats {
+            allocations: field("allocations")?,
+            bytes: field("bytes")?,
+            peak: field("peak")?,
+        })
+    }
+}
+
+impl From<&Usage> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &Usage) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+        map.insert("user_nanos".into(), JsonValue::Number(value.user.as_nanos() as f64));
+        map.insert("system_nanos".into(), JsonValue::Number(value.system.as_nanos() as f64));
+        map.insert("cpu_ratio".into(), JsonValue::Number(value.cpu_ratio));
+        map.insert("peak_rss".into(), JsonValue::Number(value.peak_rss as f64));
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Usage {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let field = |key: &str| {
+            value
+                .get::<HashMap<String, JsonValue>>()
+                .and_then(|map| map.get(key))
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected usage.{key} to be a number."))
+        };
+
+        Ok(Usage {
+            user: Duration::from_nanos(field("user_nanos")? as u64),
+            system: Duration::from_nanos(field("system_nanos")? as u64),
+            cpu_ratio: field("cpu_ratio")?,
+            peak_rss: field("peak_rss")? as u64,
+        })
+    }
+}
+
 /* -------------------------------------------------------------------------- */
 
 #[cfg(feature = "test_lib")]
@@ -188,20 +424,35 @@ mod tests {
             data: vec![
                 Timing {
                     day: day!(1),
+                    parse: None,
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
+                    part_1_allocs: None,
+                    part_2_allocs: None,
+                    part_1_usage: None,
+                    part_2_usage: None,
                     total_nanos: 3e+10,
                 },
                 Timing {
                     day: day!(2),
+                    parse: None,
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
+                    part_1_allocs: None,
+                    part_2_allocs: None,
+                    part_1_usage: None,
+                    part_2_usage: None,
                     total_nanos: 7e+10,
                 },
                 Timing {
                     day: day!(4),
+                    parse: None,
                     part_1: Some("40ms".into()),
                     part_2: None,
+                    part_1_allocs: None,
+                    part_2_allocs: None,
+                    part_1_usage: None,
+                    part_2_usage: None,
                     total_nanos: 4e+10,
                 },
             ],
@@ -245,6 +496,75 @@ mod tests {
         }
     }
 
+    mod profiles {
+        use crate::{
+            day,
+            template::{
+                machine::Machine,
+                timings::{StoredTimings, Timing, Timings},
+            },
+        };
+
+        use super::get_mock_timings;
+
+        #[test]
+        fn reads_legacy_timings() {
+            let json = r#"{ "data": [{ "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000000 }] }"#.to_string();
+            let stored = StoredTimings::try_from(json).unwrap();
+            assert_eq!(stored.profiles.len(), 1);
+            assert_eq!(stored.profiles[0].key, "unknown");
+            assert!(stored.profiles[0].machine.is_none());
+            assert_eq!(stored.profiles[0].timings.data.len(), 1);
+        }
+
+        #[test]
+        fn keeps_profiles_apart() {
+            let laptop = Machine::mock("Apple M1");
+            let ci = Machine::mock("AMD EPYC 7763 64-Core Processor");
+
+            let mut stored = StoredTimings::default();
+            stored.insert(&laptop, get_mock_timings());
+            stored.insert(
+                &ci,
+                Timings {
+                    data: vec![Timing {
+                        day: day!(5),
+                        parse: None,
+                        part_1: None,
+                        part_2: None,
+                        part_1_allocs: None,
+                        part_2_allocs: None,
+                        part_1_usage: None,
+                        part_2_usage: None,
+                        total_nanos: 0_f64,
+                    }],
+                },
+            );
+
+            assert_eq!(stored.profiles.len(), 2);
+            assert_eq!(stored.get(&laptop).data.len(), 3);
+            assert_eq!(stored.get(&ci).data.len(), 1);
+
+            stored.insert(&ci, Timings::default());
+            assert_eq!(stored.profiles.len(), 2);
+            assert_eq!(stored.get(&ci).data.len(), 0);
+        }
+
+        #[test]
+        fn roundtrips_json() {
+            let machine = Machine::mock("Apple M1");
+            let mut stored = StoredTimings::default();
+            stored.insert(&machine, get_mock_timings());
+
+            let json = tinyjson::JsonValue::from(stored).stringify().unwrap();
+            let parsed = StoredTimings::try_from(json).unwrap();
+
+            assert_eq!(parsed.profiles.len(), 1);
+            assert_eq!(parsed.profiles[0].machine, Some(machine.clone()));
+            assert_eq!(parsed.get(&machine).data.len(), 3);
+        }
+    }
+
     mod serialization {
         use super::get_mock_timings;
         use std::collections::HashMap;
@@ -279,13 +599,18 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
+                    parse: None,
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
+                    part_1_allocs: None,
+                    part_2_allocs: None,
+                    part_1_usage: None,
+                    part_2_usage: None,
                     total_nanos: 3_000_000_000_f64,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert_eq!(timings.is_day_complete(day!(1)), true);
         }
 
         #[test]
@@ -293,13 +618,18 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
+                    parse: None,
                     part_1: Some("1ms".into()),
                     part_2: None,
+                    part_1_allocs: None,
+                    part_2_allocs: None,
+                    part_1_usage: None,
+                    part_2_usage: None,
                     total_nanos: 1_000_000_000_f64,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
 
         #[test]
@@ -307,13 +637,18 @@ mod tests {
             let timings = Timings {
                 data: vec![Timing {
                     day: day!(1),
+                    parse: None,
                     part_1: None,
                     part_2: None,
+                    part_1_allocs: None,
+                    part_2_allocs: None,
+                    part_1_usage: None,
+                    part_2_usage: None,
                     total_nanos: 0.0,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert_eq!(timings.is_day_complete(day!(1)), false);
         }
     }
 
@@ -331,8 +666,13 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(3),
+                    parse: None,
                     part_1: None,
                     part_2: None,
+                    part_1_allocs: None,
+                    part_2_allocs: None,
+                    part_1_usage: None,
+                    part_2_usage: None,
                     total_nanos: 0_f64,
                 }],
             };
@@ -351,8 +691,13 @@ mod tests {
             let other = Timings {
                 data: vec![Timing {
                     day: day!(2),
+                    parse: None,
                     part_1: None,
                     part_2: None,
+                    part_1_allocs: None,
+                    part_2_allocs: None,
+                    part_1_usage: None,
+                    part_2_usage: None,
                     total_nanos: 0_f64,
                 }],
             };
@@ -0,0 +1,287 @@
+/// Debug tracing for solutions that stays out of the runner output.
+///
+/// Use the [`aoc_trace!`](crate::aoc_trace), [`aoc_debug!`](crate::aoc_debug) and [`aoc_info!`](crate::aoc_info)
+/// macros instead of `println!` or `dbg!`, and [`aoc_span!`](crate::aoc_span) to group and time the events of a
+/// code section. They are prefixed, so they do not clash with the macros of `log` or `tracing`.
+/// Events are written to `target/aoc-trace/<day>.log`, or to stderr with `AOC_TRACE_OUTPUT=stderr`.
+///
+/// Tracing is only compiled in with the `trace` feature, i.e. via `cargo solve <day> --trace`.
+/// `--trace` records every level; `AOC_TRACE=info|debug|trace` picks a level.
+/// Benchmark runs (`--time`) never trace.
+///
+/// ```ignore
+/// let _span = advent_of_code::aoc_span!("simulate obstacle {}", i);
+/// advent_of_code::aoc_debug!("guard at {position}");
+/// ```
+use std::{
+    cell::Cell,
+    fmt::{Arguments, Display},
+    fs::{self, File},
+    io::{stderr, BufWriter, Write},
+    path::PathBuf,
+    str::FromStr,
+    sync::{Mutex, OnceLock},
+    time::Instant,
+};
+
+use crate::template::Day;
+
+/// `false` when built without the `trace` feature. The macros check this first, so they compile to nothing.
+pub const ENABLED: bool = cfg!(feature = "trace");
+
+const LOG_DIR: &str = "target/aoc-trace";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Level {
+    Info,
+    Debug,
+    Trace,
+}
+
+impl FromStr for Level {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "info" => Ok(Level::Info),
+            "debug" | "1" | "true" => Ok(Level::Debug),
+            "trace" | "all" => Ok(Level::Trace),
+            _ => Err(format!(
+                "unknown trace level \"{s}\", expected info, debug or trace."
+            )),
+        }
+    }
+}
+
+impl Display for Level {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            Level::Info => "INFO",
+            Level::Debug => "DEBUG",
+            Level::Trace => "TRACE",
+        };
+        write!(f, "{name:<5}")
+    }
+}
+
+struct Tracer {
+    level: Option<Level>,
+    start: Instant,
+    sink: Mutex<Box<dyn Write + Send>>,
+}
+
+static TRACER: OnceLock<Tracer> = OnceLock::new();
+
+thread_local! {
+    static DEPTH: Cell<usize> = const { Cell::new(0) };
+}
+
+/// Reads the trace level from `AOC_TRACE` and the command line. `None` disables tracing.
+fn level_from_env() -> Option<Level> {
+    let args: Vec<String> = std::env::args().collect();
+
+    if args.iter().any(|a| a == "--time") {
+        return None;
+    }
+
+    match std::env::var("AOC_TRACE") {
+        Ok(level) if !level.is_empty() => match level.parse() {
+            Ok(level) => Some(level),
+            Err(e) => {
+                eprintln!("{e}");
+                None
+            }
+        },
+        _ => args.iter().any(|a| a == "--trace").then_some(Level::Trace),
+    }
+}
+
+fn create_tracer(day: Option<Day>) -> Tracer {
+    let level = level_from_env();
+    let to_stderr = std::env::var("AOC_TRACE_OUTPUT").is_ok_and(|o| o == "stderr");
+
+    let sink: Box<dyn Write + Send> = match (level, day) {
+        (Some(_), Some(day)) if !to_stderr => match open_log(day) {
+            Ok((path, file)) => {
+                eprintln!("Tracing to {}", path.display());
+                Box::new(BufWriter::new(file))
+            }
+            Err(e) => {
+                eprintln!("Failed to open trace log, tracing to stderr: {e}");
+                Box::new(stderr())
+            }
+        },
+        _ => Box::new(stderr()),
+    };
+
+    Tracer {
+        level,
+        start: Instant::now(),
+        sink: Mutex::new(sink),
+    }
+}
+
+fn open_log(day: Day) -> std::io::Result<(PathBuf, File)> {
+    fs::create_dir_all(LOG_DIR)?;
+    let path = PathBuf::from(LOG_DIR).join(format!("{day}.log"));
+    let file = File::create(&path)?;
+    Ok((path, file))
+}
+
+fn tracer() -> &'static Tracer {
+    TRACER.get_or_init(|| create_tracer(None))
+}
+
+/// Sets up tracing for `day`. Called by [`day_module!`](crate::day_module) when the `trace` feature is enabled.
+pub fn init(day: Day) {
+    let _ = TRACER.set(create_tracer(Some(day)));
+}
+
+/// Returns `true` if events of `level` are recorded.
+pub fn is_enabled(level: Level) -> bool {
+    ENABLED && tracer().level.is_some_and(|max| level <= max)
+}
+
+/// Records an event. Prefer the macros, which skip formatting when tracing is disabled.
+pub fn log(level: Level, args: Arguments) {
+    let tracer = tracer();
+    let elapsed = tracer.start.elapsed();
+    let indent = DEPTH.with(Cell::get) * 2;
+
+    if let Ok(mut sink) = tracer.sink.lock() {
+        let _ = writeln!(
+            sink,
+            "[{:>10.3}ms {level}] {:indent$}{args}",
+            elapsed.as_secs_f64() * 1000.0,
+            ""
+        );
+    }
+}
+
+/// Flushes buffered events. Called by the runner after each part.
+pub fn flush() {
+    if let Some(tracer) = TRACER.get() {
+        if let Ok(mut sink) = tracer.sink.lock() {
+            let _ = sink.flush();
+        }
+    }
+}
+
+/// Indents the events recorded while it is alive and logs its duration when dropped.
+#[must_use = "a span ends when it is dropped"]
+pub struct Span {
+    inner: Option<(String, Instant)>,
+}
+
+impl Span {
+    /// A span that records nothing.
+    pub fn disabled() -> Self {
+        Self { inner: None }
+    }
+
+    /// Opens a span. Prefer the [`aoc_span!`](crate::aoc_span) macro.
+    pub fn enter(name: String) -> Self {
+        log(Level::Debug, format_args!("> {name}"));
+        DEPTH.with(|d| d.set(d.get() + 1));
+        Self {
+            inner: Some((name, Instant::now())),
+        }
+    }
+}
+
+impl Drop for Span {
+    fn drop(&mut self) {
+        if let Some((name, start)) = self.inner.take() {
+            DEPTH.with(|d| d.set(d.get().saturating_sub(1)));
+            log(
+                Level::Debug,
+                format_args!("< {name} ({:.3?})", start.elapsed()),
+            );
+        }
+    }
+}
+
+/// Records an event at the given level, e.g. `aoc_event!(Level::Info, "{x}")`.
+#[macro_export]
+macro_rules! aoc_event {
+    ($level:expr, $($arg:tt)+) => {
+        if $crate::template::trace::ENABLED && $crate::template::trace::is_enabled($level) {
+            $crate::template::trace::log($level, format_args!($($arg)+));
+        }
+    };
+}
+
+/// Records a high-level event, e.g. a result per section.
+#[macro_export]
+macro_rules! aoc_info {
+    ($($arg:tt)+) => {
+        $crate::aoc_event!($crate::template::trace::Level::Info, $($arg)+)
+    };
+}
+
+/// Records a debug event, a replacement for `dbg!` and `println!`.
+#[macro_export]
+macro_rules! aoc_debug {
+    ($($arg:tt)+) => {
+        $crate::aoc_event!($crate::template::trace::Level::Debug, $($arg)+)
+    };
+}
+
+/// Records a detailed event, e.g. inside hot loops.
+#[macro_export]
+macro_rules! aoc_trace {
+    ($($arg:tt)+) => {
+        $crate::aoc_event!($crate::template::trace::Level::Trace, $($arg)+)
+    };
+}
+
+/// Opens a [`Span`](crate::template::trace::Span) at debug level. Bind it to a variable to keep it open.
+#[macro_export]
+macro_rules! aoc_span {
+    ($($arg:tt)+) => {
+        if $crate::template::trace::ENABLED
+            && $crate::template::trace::is_enabled($crate::template::trace::Level::Debug)
+        {
+            $crate::template::trace::Span::enter(format!($($arg)+))
+        } else {
+            $crate::template::trace::Span::disabled()
+        }
+    };
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::Level;
+
+    #[test]
+    fn parses_levels() {
+        assert_eq!("info".parse(), Ok(Level::Info));
+        assert_eq!("DEBUG".parse(), Ok(Level::Debug));
+        assert_eq!("1".parse(), Ok(Level::Debug));
+        assert_eq!("all".parse(), Ok(Level::Trace));
+        assert!("verbose".parse::<Level>().is_err());
+    }
+
+    #[test]
+    fn orders_levels_by_verbosity() {
+        assert!(Level::Info < Level::Debug);
+        assert!(Level::Debug < Level::Trace);
+    }
+
+    #[test]
+    fn macros_compile_away_without_feature() {
+        // the arguments must not be evaluated when tracing is disabled.
+        let evaluated = std::cell::Cell::new(false);
+        let arg = || {
+            evaluated.set(true);
+            1
+        };
+        if !super::ENABLED {
+            crate::aoc_debug!("{}", arg());
+            let _span = crate::aoc_span!("span {}", arg());
+            assert!(!evaluated.get());
+        }
+    }
+}
@@ -0,0 +1,361 @@
+/// Visualization hooks for simulation-style solutions.
+///
+/// Solutions obtain a [`Visualizer`] via [`visualizer()`] and feed it [`Frame`]s.
+/// Frames are only displayed when the solution was built with the `visualize` feature and started with
+/// `--visualize`, i.e. via `cargo solve <day> --visualize`. Otherwise, [`visualizer()`] returns a
+/// [`NoopVisualizer`] and the calls compile away.
+///
+/// ```ignore
+/// let mut vis = visualizer();
+/// vis.frame_with(|| Frame::from_fn(width, height, |x, y| grid[(y, x)]).with_caption("step 1"));
+/// ```
+use std::fmt::Display;
+
+/// A single annotated point, drawn on top of a frame's cells.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Point {
+    pub x: usize,
+    pub y: usize,
+    pub glyph: char,
+    pub label: Option<String>,
+}
+
+impl Point {
+    pub fn new(x: usize, y: usize, glyph: char) -> Self {
+        Self {
+            x,
+            y,
+            glyph,
+            label: None,
+        }
+    }
+
+    #[must_use]
+    pub fn with_label(mut self, label: impl Display) -> Self {
+        self.label = Some(label.to_string());
+        self
+    }
+}
+
+/// A grid of characters with optional annotated points drawn on top.
+/// `x` is the column and `y` the row of a cell.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Frame {
+    pub width: usize,
+    pub height: usize,
+    cells: Vec<char>,
+    points: Vec<Point>,
+    caption: Option<String>,
+    focus: Option<(usize, usize)>,
+}
+
+impl Frame {
+    /// Creates a frame filled with `background`.
+    pub fn new(width: usize, height: usize, background: char) -> Self {
+        Self {
+            width,
+            height,
+            cells: vec![background; width * height],
+            points: vec![],
+            caption: None,
+            focus: None,
+        }
+    }
+
+    /// Creates a frame by calling `f(x, y)` for every cell.
+    pub fn from_fn(width: usize, height: usize, f: impl Fn(usize, usize) -> char) -> Self {
+        let mut frame = Self::new(width, height, ' ');
+        for y in 0..height {
+            for x in 0..width {
+                frame.cells[y * width + x] = f(x, y);
+            }
+        }
+        frame
+    }
+
+    /// Returns the character at a cell, ignoring annotated points.
+    pub fn get(&self, x: usize, y: usize) -> Option<char> {
+        (x < self.width && y < self.height).then(|| self.cells[y * self.width + x])
+    }
+
+    /// Sets a cell. Coordinates outside of the frame are ignored.
+    pub fn set(&mut self, x: usize, y: usize, c: char) {
+        if x < self.width && y < self.height {
+            self.cells[y * self.width + x] = c;
+        }
+    }
+
+    pub fn add_point(&mut self, point: Point) {
+        self.points.push(point);
+    }
+
+    #[must_use]
+    pub fn with_point(mut self, point: Point) -> Self {
+        self.add_point(point);
+        self
+    }
+
+    #[must_use]
+    pub fn with_caption(mut self, caption: impl Display) -> Self {
+        self.caption = Some(caption.to_string());
+        self
+    }
+
+    /// Keeps the cell at `(x, y)` in view when the frame is larger than the display.
+    #[must_use]
+    pub fn with_focus(mut self, x: usize, y: usize) -> Self {
+        self.focus = Some((x, y));
+        self
+    }
+
+    pub fn points(&self) -> &[Point] {
+        &self.points
+    }
+
+    pub fn caption(&self) -> Option<&str> {
+        self.caption.as_deref()
+    }
+
+    /// Returns the cells with annotated points drawn on top, row by row.
+    pub fn composed(&self) -> Vec<char> {
+        let mut cells = self.cells.clone();
+        for p in &self.points {
+            if p.x < self.width && p.y < self.height {
+                cells[p.y * self.width + p.x] = p.glyph;
+            }
+        }
+        cells
+    }
+
+    /// Renders the frame as text, cropped to `max_width` x `max_height` cells around the focus.
+    pub fn render(&self, max_width: usize, max_height: usize) -> String {
+        let (fx, fy) = self.focus.unwrap_or((0, 0));
+        let window = |size: usize, max: usize, focus: usize| {
+            if size <= max {
+                0..size
+            } else {
+                let start = focus.saturating_sub(max / 2).min(size - max);
+                start..start + max
+            }
+        };
+        let columns = window(self.width, max_width, fx);
+        let rows = window(self.height, max_height, fy);
+
+        let cells = self.composed();
+        let mut out = String::new();
+
+        for y in rows.clone() {
+            out.extend(columns.clone().map(|x| cells[y * self.width + x]));
+            out.push('\n');
+        }
+
+        if columns.len() < self.width || rows.len() < self.height {
+            out.push_str(&format!(
+                "rows {}-{} of {} | columns {}-{} of {}\n",
+                rows.start,
+                rows.end - 1,
+                self.height,
+                columns.start,
+                columns.end - 1,
+                self.width
+            ));
+        }
+
+        for p in self.points.iter().filter(|p| p.label.is_some()) {
+            out.push_str(&format!(
+                "{} ({}, {}): {}\n",
+                p.glyph,
+                p.x,
+                p.y,
+                p.label.as_deref().unwrap_or_default()
+            ));
+        }
+
+        if let Some(caption) = &self.caption {
+            out.push_str(caption);
+            out.push('\n');
+        }
+
+        out
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Receives frames from a solution.
+pub trait Visualizer {
+    /// Returns `false` if frames are discarded.
+    fn is_enabled(&self) -> bool;
+
+    /// Displays a frame.
+    fn frame(&mut self, frame: &Frame);
+
+    /// Builds and displays a frame, but only if the visualizer is enabled.
+    /// Prefer this over [`Visualizer::frame`] to avoid building frames in normal runs.
+    #[inline(always)]
+    fn frame_with(&mut self, build: impl FnOnce() -> Frame)
+    where
+        Self: Sized,
+    {
+        if self.is_enabled() {
+            self.frame(&build());
+        }
+    }
+
+    /// Displays annotated points on an empty `width` x `height` grid.
+    #[inline(always)]
+    fn points_with(&mut self, width: usize, height: usize, build: impl FnOnce() -> Vec<Point>)
+    where
+        Self: Sized,
+    {
+        if self.is_enabled() {
+            let mut frame = Frame::new(width, height, '.');
+            for point in build() {
+                frame.add_point(point);
+            }
+            self.frame(&frame);
+        }
+    }
+}
+
+/// Discards all frames.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct NoopVisualizer;
+
+impl Visualizer for NoopVisualizer {
+    #[inline(always)]
+    fn is_enabled(&self) -> bool {
+        false
+    }
+
+    #[inline(always)]
+    fn frame(&mut self, _frame: &Frame) {}
+}
+
+#[cfg(feature = "visualize")]
+pub use terminal_visualizer::TerminalVisualizer;
+
+#[cfg(feature = "visualize")]
+mod terminal_visualizer {
+    use std::{
+        io::{stderr, Write},
+        thread,
+        time::Duration,
+    };
+
+    use super::{Frame, Visualizer};
+    use crate::template::terminal;
+
+    const DEFAULT_DELAY_MS: u64 = 50;
+    const ANSI_CLEAR: &str = "\x1b[2J\x1b[H";
+
+    /// Draws frames to stderr, so they do not mix with the runner output.
+    /// The delay between frames can be set with `AOC_VISUALIZE_DELAY_MS`.
+    #[derive(Clone, Debug)]
+    pub struct TerminalVisualizer {
+        enabled: bool,
+        delay: Duration,
+        frames: usize,
+    }
+
+    impl TerminalVisualizer {
+        pub fn new(enabled: bool) -> Self {
+            let delay = std::env::var("AOC_VISUALIZE_DELAY_MS")
+                .ok()
+                .and_then(|d| d.parse().ok())
+                .unwrap_or(DEFAULT_DELAY_MS);
+
+            Self {
+                enabled,
+                delay: Duration::from_millis(delay),
+                frames: 0,
+            }
+        }
+    }
+
+    impl Visualizer for TerminalVisualizer {
+        fn is_enabled(&self) -> bool {
+            self.enabled
+        }
+
+        fn frame(&mut self, frame: &Frame) {
+            if !self.enabled {
+                return;
+            }
+
+            self.frames += 1;
+
+            // leave room for the frame counter, the caption and the crop notice.
+            let rendered = frame.render(
+                terminal::width(),
+                terminal::height()
+                    .saturating_sub(4 + frame.points().len())
+                    .max(1),
+            );
+
+            let mut stderr = stderr().lock();
+            let _ = writeln!(stderr, "{ANSI_CLEAR}{rendered}frame {}", self.frames);
+            let _ = stderr.flush();
+
+            thread::sleep(self.delay);
+        }
+    }
+}
+
+/// Returns the visualizer for the current run.
+///
+/// This is a [`TerminalVisualizer`] when built with the `visualize` feature and started with `--visualize`.
+/// It is never enabled while benchmarking.
+#[cfg(feature = "visualize")]
+pub fn visualizer() -> TerminalVisualizer {
+    let args: Vec<String> = std::env::args().collect();
+    let enabled = args.iter().any(|a| a == "--visualize") && !args.iter().any(|a| a == "--time");
+    TerminalVisualizer::new(enabled)
+}
+
+/// Returns the visualizer for the current run.
+///
+/// Without the `visualize` feature, this is a [`NoopVisualizer`].
+#[cfg(not(feature = "visualize"))]
+#[inline(always)]
+pub fn visualizer() -> NoopVisualizer {
+    NoopVisualizer
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(feature = "test_lib")]
+mod tests {
+    use super::{Frame, NoopVisualizer, Point, Visualizer};
+
+    #[test]
+    fn renders_frames() {
+        let frame = Frame::from_fn(3, 2, |x, y| if x == y { '#' } else { '.' })
+            .with_point(Point::new(2, 1, '^').with_label("guard"))
+            .with_caption("step 1");
+        assert_eq!(frame.render(80, 24), "#..\n.#^\n^ (2, 1): guard\nstep 1\n");
+    }
+
+    #[test]
+    fn crops_around_focus() {
+        let frame = Frame::from_fn(10, 10, |x, y| if (x, y) == (8, 7) { 'X' } else { '.' })
+            .with_focus(8, 7);
+        let rendered = frame.render(4, 3);
+        let lines: Vec<&str> = rendered.lines().collect();
+        assert_eq!(lines[..3], ["....", "..X.", "...."]);
+        assert_eq!(lines[3], "rows 6-8 of 10 | columns 6-9 of 10");
+    }
+
+    #[test]
+    fn ignores_points_outside_the_frame() {
+        let frame = Frame::new(2, 1, '.').with_point(Point::new(5, 5, '#'));
+        assert_eq!(frame.composed(), vec!['.', '.']);
+    }
+
+    #[test]
+    fn noop_visualizer_does_not_build_frames() {
+        let mut vis = NoopVisualizer;
+        vis.frame_with(|| panic!("frame should not be built."));
+        assert!(!vis.is_enabled());
+    }
+}
@@ -0,0 +1,31 @@
+//! Differential tests: each optimized part is compared to a reference implementation on generated inputs.
+//! They are ignored by `cargo test`, run them with `cargo diff-test [day]`.
+
+mod reference;
+
+use advent_of_code::template::differential::Differential;
+
+mod day09 {
+  use super::{reference, Differential};
+  use advent_of_code::days::day09;
+
+  #[test]
+  #[ignore = "differential test, run with `cargo diff-test 09`"]
+  fn part_one() {
+    Differential::default().assert(
+      reference::day09::generate,
+      day09::part_one_no_opt,
+      reference::day09::part_one,
+    );
+  }
+
+  #[test]
+  #[ignore = "differential test, run with `cargo diff-test 09`"]
+  fn part_two() {
+    Differential::default().assert(
+      reference::day09::generate,
+      day09::part_two_no_opt,
+      reference::day09::part_two,
+    );
+  }
+}
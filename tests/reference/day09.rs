@@ -0,0 +1,69 @@
+use advent_of_code::template::differential::Rng;
+
+/// Generates a disk map: alternating file and free space lengths, files are never empty.
+pub fn generate(rng: &mut Rng) -> String {
+  let files = rng.range(1, 30);
+  let mut map = String::new();
+  for file in 0..files {
+    map.push_str(&rng.range(1, 9).to_string());
+    if file + 1 < files {
+      map.push_str(&rng.range(0, 9).to_string());
+    }
+  }
+  map.push('\n');
+  map
+}
+
+/// Expands the disk map to one entry per block, holding the file id of the block.
+fn blocks(input: &str) -> Vec<Option<u64>> {
+  let mut blocks = vec![];
+  for (index, c) in input.trim().chars().enumerate() {
+    let len = c.to_digit(10).expect("invalid digit") as usize;
+    let id = (index % 2 == 0).then_some(index as u64 / 2);
+    blocks.extend(std::iter::repeat_n(id, len));
+  }
+  blocks
+}
+
+fn checksum(blocks: &[Option<u64>]) -> u64 {
+  blocks
+    .iter()
+    .enumerate()
+    .filter_map(|(position, id)| id.map(|id| position as u64 * id))
+    .sum()
+}
+
+/// Moves single blocks from the end of the disk to the leftmost free block.
+pub fn part_one(input: &str) -> u64 {
+  let mut blocks = blocks(input);
+  while let (Some(free), Some(last)) = (
+    blocks.iter().position(Option::is_none),
+    blocks.iter().rposition(Option::is_some),
+  ) {
+    if last < free {
+      break;
+    }
+    blocks.swap(free, last);
+  }
+  checksum(&blocks)
+}
+
+/// Moves whole files, highest id first, to the leftmost free span that fits them.
+pub fn part_two(input: &str) -> u64 {
+  let mut blocks = blocks(input);
+  let max_id = blocks.iter().flatten().copied().max().unwrap_or(0);
+
+  for id in (0..=max_id).rev() {
+    let start = blocks.iter().position(|b| *b == Some(id)).unwrap();
+    let len = blocks.iter().filter(|b| **b == Some(id)).count();
+
+    let target = (0..start)
+      .find(|&i| i + len <= start && blocks[i..i + len].iter().all(Option::is_none));
+    if let Some(target) = target {
+      for offset in 0..len {
+        blocks.swap(target + offset, start + offset);
+      }
+    }
+  }
+  checksum(&blocks)
+}
@@ -0,0 +1,3 @@
+//! Straightforward reference implementations and input generators for the differential tests.
+
+pub mod day09;